@@ -2,7 +2,9 @@
 
 use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
 
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+use serde::Serialize;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub struct Point<T> {
     pub x: T,
     pub y: T,
@@ -14,7 +16,7 @@ impl<T> Point<T> {
     }
 }
 
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub struct Size<T> {
     pub width: T,
     pub height: T,
@@ -38,7 +40,7 @@ impl<T> From<Size<T>> for Point<T> {
     }
 }
 
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub struct Rect<T> {
     pub origin: Point<T>,
     pub size: Size<T>,
@@ -75,6 +77,32 @@ impl<T: Copy> Rect<T> {
     }
 }
 
+impl<T: Copy + Add<Output = T> + PartialOrd> Rect<T> {
+    pub fn right(&self) -> T {
+        self.origin.x + self.size.width
+    }
+
+    pub fn bottom(&self) -> T {
+        self.origin.y + self.size.height
+    }
+
+    /// Whether `other` lies entirely inside this rect, edges included.
+    pub fn contains(&self, other: &Self) -> bool {
+        other.origin.x >= self.origin.x
+            && other.origin.y >= self.origin.y
+            && other.right() <= self.right()
+            && other.bottom() <= self.bottom()
+    }
+
+    /// Whether the rects share some area, rects that only touch don't intersect.
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.origin.x < other.right()
+            && other.origin.x < self.right()
+            && self.origin.y < other.bottom()
+            && other.origin.y < self.bottom()
+    }
+}
+
 macro_rules! impl_math {
     ($type:ty, $first_prop:ident, $second_prop:ident) => {
         impl<T: Add<Output = T>> Add for $type {
@@ -0,0 +1,138 @@
+use std::iter;
+
+use muzui_styling::GridFlow;
+
+use super::{
+    lexer::Token,
+    parser::{Parse, ParseError, Parser, Result},
+};
+
+/// Attributes of a `Grid` that belong to the element rather than its style.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GridAttributes {
+    pub flow: Option<GridFlow>,
+    /// A name for every cell of every row, `.` for cells outside of any area.
+    pub areas: Option<Vec<Vec<String>>>,
+    pub row_gap: Option<f32>,
+    pub column_gap: Option<f32>,
+}
+
+/// Parses `row` or `column`, optionally followed by `dense`.
+impl Parse for GridFlow {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        let column = parser.consume_map(|token| {
+            token.try_as_ident().and_then(|ident| match ident {
+                "row" => Some(false),
+                "column" => Some(true),
+                _ => None,
+            })
+        })?;
+
+        Ok(match (column, parser.try_consume(&Token::ident("dense"))) {
+            (false, false) => Self::Row,
+            (true, false) => Self::Column,
+            (false, true) => Self::RowDense,
+            (true, true) => Self::ColumnDense,
+        })
+    }
+}
+
+/// Parses adjacent strings, one per row, e.g. `"header header" "sidebar main"`.
+fn parse_areas(parser: &mut Parser) -> Result<Vec<Vec<String>>> {
+    let first = parser
+        .consume_if(Token::is_string)
+        .map(Token::into_string)?;
+
+    Ok(iter::once(first)
+        .chain(iter::from_fn(|| {
+            parser.next_if(Token::is_string).map(Token::into_string)
+        }))
+        .map(|row| row.split_whitespace().map(ToString::to_string).collect())
+        .collect())
+}
+
+/// Parses the value of the grid attribute `name` into `attributes`.
+///
+/// # Errors
+///
+/// Returns error if the attribute is unknown or its value failed to parse
+pub fn parse_grid_attribute(
+    parser: &mut Parser,
+    name: &str,
+    attributes: &mut GridAttributes,
+) -> Result<()> {
+    match name {
+        "flow" => attributes.flow = Some(GridFlow::parse(parser)?),
+        "areas" => attributes.areas = Some(parse_areas(parser)?),
+        "row-gap" => attributes.row_gap = Some(parser.consume_map(Token::try_as_f32)?),
+        "column-gap" => attributes.column_gap = Some(parser.consume_map(Token::try_as_f32)?),
+        name => return Err(ParseError::new(format!("Unknown grid attribute {name}"))),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use muzui_styling::GridFlow;
+
+    use super::{parse_grid_attribute, GridAttributes};
+    use crate::{
+        lexer::{Lexer, Token},
+        parser::{Parser, Result},
+    };
+
+    fn parse(source: &str) -> Result<GridAttributes> {
+        let mut parser = Parser::new(Lexer::parse(source));
+        let mut attributes = GridAttributes::default();
+
+        loop {
+            let name = parser.consume_if(Token::is_ident).map(Token::into_ident)?;
+
+            parser.consume(&Token::Colon)?;
+
+            parse_grid_attribute(&mut parser, &name, &mut attributes)?;
+
+            if !parser.try_consume(&Token::Comma) {
+                break Ok(attributes);
+            }
+        }
+    }
+
+    #[test]
+    fn test_grid_attributes() {
+        let attributes = parse(
+            r#"flow: column dense, areas: "header header" "sidebar main", row-gap: 8, column-gap: 4.5"#,
+        )
+        .expect("failed to parse grid attributes");
+
+        assert_eq!(attributes.flow, Some(GridFlow::ColumnDense));
+        assert_eq!(
+            attributes.areas,
+            Some(vec![
+                vec!["header".to_string(), "header".to_string()],
+                vec!["sidebar".to_string(), "main".to_string()],
+            ])
+        );
+        assert_eq!(attributes.row_gap, Some(8.0));
+        assert_eq!(attributes.column_gap, Some(4.5));
+
+        let attributes = parse("flow: row").expect("failed to parse grid attributes");
+
+        assert_eq!(attributes.flow, Some(GridFlow::Row));
+        assert_eq!(attributes.areas, None);
+    }
+
+    #[test]
+    fn test_invalid_grid_attributes() {
+        for source in [
+            "flow: dense",
+            "flow: diagonal",
+            "areas: header",
+            "row-gap: wide",
+            "gap: 8",
+        ] {
+            assert!(parse(source).is_err(), "{source}");
+        }
+    }
+}
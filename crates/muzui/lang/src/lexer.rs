@@ -1,8 +1,50 @@
 use std::{
     fmt::{self, Write},
     iter, mem,
+    str::Chars,
 };
 
+/// A unit suffix directly following a number, e.g. the `vw` of `50vw`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Unit {
+    Px,
+    Vw,
+    Vh,
+    Vmin,
+    Vmax,
+    Em,
+    Rem,
+}
+
+impl Unit {
+    #[must_use]
+    pub fn from_suffix(value: &str) -> Option<Self> {
+        match value {
+            "px" => Some(Self::Px),
+            "vw" => Some(Self::Vw),
+            "vh" => Some(Self::Vh),
+            "vmin" => Some(Self::Vmin),
+            "vmax" => Some(Self::Vmax),
+            "em" => Some(Self::Em),
+            "rem" => Some(Self::Rem),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_suffix(&self) -> &'static str {
+        match self {
+            Self::Px => "px",
+            Self::Vw => "vw",
+            Self::Vh => "vh",
+            Self::Vmin => "vmin",
+            Self::Vmax => "vmax",
+            Self::Em => "em",
+            Self::Rem => "rem",
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum StringPart {
     String(String),
@@ -16,6 +58,7 @@ pub enum Token {
     String(String),
     Float(f32),
     Integer(i64),
+    Dimension(f32, Unit),
     Boolean(bool),
     BracketOpen,
     BracketClose,
@@ -42,6 +85,7 @@ impl fmt::Display for Token {
             Self::Float(value) => value.fmt(f),
             Self::Integer(value) => value.fmt(f),
             Self::Boolean(value) => value.fmt(f),
+            Self::Dimension(value, unit) => write!(f, "{value}{}", unit.as_suffix()),
             Self::BracketOpen => f.write_char('['),
             Self::BracketClose => f.write_char(']'),
             Self::BraceOpen => f.write_char('{'),
@@ -93,6 +137,23 @@ impl Token {
         matches!(self, Self::Integer(_) | Self::Float(_))
     }
 
+    /// Returns `true` if the token is [`Dimension`].
+    ///
+    /// [`Dimension`]: Token::Dimension
+    #[must_use]
+    pub const fn is_dimension(&self) -> bool {
+        matches!(self, Self::Dimension(..))
+    }
+
+    #[must_use]
+    pub const fn try_as_dimension(&self) -> Option<(f32, Unit)> {
+        if let Self::Dimension(value, unit) = self {
+            Some((*value, *unit))
+        } else {
+            None
+        }
+    }
+
     #[must_use]
     pub fn into_string(self) -> String {
         if let Self::String(value) = self {
@@ -112,6 +173,7 @@ impl Token {
     }
 
     #[must_use]
+    #[allow(clippy::cast_precision_loss)]
     pub fn into_f32(self) -> f32 {
         if let Self::Float(value) = self {
             value
@@ -159,7 +221,7 @@ impl Token {
     }
 
     #[must_use]
-    pub fn try_as_str(&self) -> Option<&str> {
+    pub const fn try_as_str(&self) -> Option<&str> {
         if let Self::String(value) = self {
             Some(value.as_str())
         } else {
@@ -168,7 +230,7 @@ impl Token {
     }
 
     #[must_use]
-    pub fn try_as_ident(&self) -> Option<&str> {
+    pub const fn try_as_ident(&self) -> Option<&str> {
         if let Self::Ident(value) = self {
             Some(value.as_str())
         } else {
@@ -177,6 +239,7 @@ impl Token {
     }
 
     #[must_use]
+    #[allow(clippy::cast_precision_loss)]
     pub const fn try_as_f32(&self) -> Option<f32> {
         if let Self::Float(value) = self {
             Some(*value)
@@ -242,16 +305,36 @@ impl Token {
 pub struct Lexer;
 
 impl Lexer {
+    /// Turns `number` into a [`Token::Dimension`] if it's followed by a known unit suffix.
+    ///
+    /// Unknown suffixes are left for the next token.
+    fn parse_unit(chars: &mut iter::Peekable<Chars>, number: Token) -> Token {
+        let mut lookahead = chars.clone();
+
+        let suffix =
+            iter::from_fn(|| lookahead.next_if(char::is_ascii_alphabetic)).collect::<String>();
+
+        match (number.try_as_f32(), Unit::from_suffix(&suffix)) {
+            (Some(value), Some(unit)) => {
+                *chars = lookahead;
+
+                Token::Dimension(value, unit)
+            }
+            _ => number,
+        }
+    }
+
     /// # Panics
     ///
     /// Can panic if number failed to parse
+    #[allow(clippy::too_many_lines)]
     pub fn parse<T: AsRef<str>>(data: T) -> Vec<Token> {
         let mut tokens = vec![];
         let mut chars = data.as_ref().chars().peekable();
 
         while let Some(character) = chars.next() {
             match character {
-                character if character.is_ascii_whitespace() => continue,
+                character if character.is_ascii_whitespace() => {}
                 '[' => tokens.push(Token::BracketOpen),
                 ']' => tokens.push(Token::BracketClose),
                 '{' => tokens.push(Token::BraceOpen),
@@ -293,13 +376,19 @@ impl Lexer {
                             value.push('.');
                             value.push_str(&after);
 
-                            tokens.push(Token::Float(value.parse().unwrap()));
+                            tokens.push(Self::parse_unit(
+                                &mut chars,
+                                Token::Float(value.parse().unwrap()),
+                            ));
                         }
 
                         continue;
                     }
 
-                    tokens.push(Token::Integer(value.parse().unwrap()));
+                    tokens.push(Self::parse_unit(
+                        &mut chars,
+                        Token::Integer(value.parse().unwrap()),
+                    ));
                 }
                 ':' => tokens.push(Token::Colon),
                 '+' => tokens.push(Token::Plus),
@@ -311,11 +400,13 @@ impl Lexer {
                             }))
                             .collect::<String>();
 
-                        if value.contains('.') {
-                            tokens.push(Token::Float(value.parse().unwrap()));
+                        let number = if value.contains('.') {
+                            Token::Float(value.parse().unwrap())
                         } else {
-                            tokens.push(Token::Integer(value.parse().unwrap()));
-                        }
+                            Token::Integer(value.parse().unwrap())
+                        };
+
+                        tokens.push(Self::parse_unit(&mut chars, number));
                     } else {
                         tokens.push(Token::Minus);
                     }
@@ -388,3 +479,52 @@ impl Lexer {
         tokens
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Lexer, Token, Unit};
+
+    #[test]
+    fn test_units() {
+        assert_eq!(
+            Lexer::parse("width: 50vw, height: 1.5em, padding: -2rem 10px 3vmin 4vmax, y: 5vh"),
+            vec![
+                Token::ident("width"),
+                Token::Colon,
+                Token::Dimension(50.0, Unit::Vw),
+                Token::Comma,
+                Token::ident("height"),
+                Token::Colon,
+                Token::Dimension(1.5, Unit::Em),
+                Token::Comma,
+                Token::ident("padding"),
+                Token::Colon,
+                Token::Dimension(-2.0, Unit::Rem),
+                Token::Dimension(10.0, Unit::Px),
+                Token::Dimension(3.0, Unit::Vmin),
+                Token::Dimension(4.0, Unit::Vmax),
+                Token::Comma,
+                Token::ident("y"),
+                Token::Colon,
+                Token::Dimension(5.0, Unit::Vh),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_suffix() {
+        assert_eq!(
+            Lexer::parse("50% 10deg 0..5"),
+            vec![
+                Token::Integer(50),
+                Token::Percent,
+                Token::Integer(10),
+                Token::ident("deg"),
+                Token::Integer(0),
+                Token::Dot,
+                Token::Dot,
+                Token::Integer(5),
+            ]
+        );
+    }
+}
@@ -8,10 +8,7 @@ mod tests {
 
     use material_colors::{color::Argb, theme::ThemeBuilder};
 
-    use crate::muzui::{
-        language::{parser::Parser, program::parse_node},
-        RenderContext,
-    };
+    use crate::muzui::language::{parser::Parser, program::parse_node};
 
     use super::lexer::Lexer;
 
@@ -85,24 +82,4 @@ mod tests {
 
         println!("{:#?}", parse_node(&mut parser, &theme, None));
     }
-
-    #[test]
-    fn test_result() {
-        let mut parser = Parser::new(Lexer::parse(DATA));
-        let theme = ThemeBuilder::with_source(Argb::from_u32(0xFFFF0000))
-            .build()
-            .schemes
-            .dark
-            .into_iter()
-            .collect::<HashMap<_, _>>();
-        let node = parse_node(&mut parser, &theme, None).expect("failed to parse node");
-
-        let mut context = RenderContext::new(1000, 1000).unwrap();
-
-        context.render(&node);
-
-        if let Some(data) = context.encode() {
-            std::fs::write("/home/aiving/test.png", &data).unwrap();
-        }
-    }
 }
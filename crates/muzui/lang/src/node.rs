@@ -0,0 +1,522 @@
+use muzui_styling::{Style, Stylesheet};
+use serde_json::Value;
+
+use super::{
+    grid::{parse_grid_attribute, GridAttributes},
+    lexer::{Lexer, StringPart, Token},
+    parser::{ParseError, Parser, Result},
+    style::{parse_property, parse_stylesheet, Theme},
+};
+
+/// A positional argument of a node, e.g. the text of `Text("...")`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Argument {
+    Text(String),
+    Number(f32),
+}
+
+/// A node of a layout with its loops expanded and its data filled in.
+#[derive(Debug, Default, Clone)]
+pub struct SourceNode {
+    pub element: String,
+    pub args: Vec<Argument>,
+    pub style: Style,
+    pub spacing: Option<f32>,
+    pub grid: GridAttributes,
+    pub children: Vec<Self>,
+}
+
+#[derive(Debug)]
+enum Index {
+    Key(String),
+    Item(usize),
+}
+
+#[derive(Debug)]
+enum Part {
+    Text(String),
+    Path(Vec<Index>),
+}
+
+#[derive(Debug)]
+enum Expression {
+    Text(Vec<Part>),
+    Number(f32),
+    Path(Vec<Index>),
+}
+
+#[derive(Debug)]
+enum Statement {
+    Node(Box<SourceNode>, Vec<Expression>, Vec<Self>),
+    For(String, Vec<Index>, Vec<Self>),
+}
+
+const GRID_ATTRIBUTES: [&str; 4] = ["flow", "areas", "row-gap", "column-gap"];
+
+/// Parses `name(.name | [index])*`, e.g. `characters.list[0].name`.
+fn parse_path(parser: &mut Parser) -> Result<Vec<Index>> {
+    let mut path = vec![Index::Key(
+        parser.consume_if(Token::is_ident).map(Token::into_ident)?,
+    )];
+
+    while let Some(token) = parser.next_if(|token| matches!(token, Token::Dot | Token::BracketOpen))
+    {
+        path.push(if token == Token::Dot {
+            Index::Key(parser.consume_if(Token::is_ident).map(Token::into_ident)?)
+        } else {
+            let index = parser
+                .consume_map(|token| token.try_as_i64().and_then(|value| value.try_into().ok()))?;
+
+            parser.consume(&Token::BracketClose)?;
+
+            Index::Item(index)
+        });
+    }
+
+    Ok(path)
+}
+
+fn parse_expression(parser: &mut Parser) -> Result<Expression> {
+    if parser.check_if(Token::is_formatted_string) {
+        let parts = parser
+            .consume_if(Token::is_formatted_string)
+            .map(Token::into_formatted_string)?;
+
+        parts
+            .into_iter()
+            .map(|part| match part {
+                StringPart::String(value) => Ok(Part::Text(value)),
+                StringPart::Formatted(tokens) => {
+                    let mut parser = Parser::new(tokens);
+                    let path = parse_path(&mut parser)?;
+
+                    parser.peek().map_or(Ok(Part::Path(path)), |token| {
+                        Err(ParseError::new(format!("Unexpected {token}")))
+                    })
+                }
+            })
+            .collect::<Result<_>>()
+            .map(Expression::Text)
+    } else if parser.check_if(Token::is_string) {
+        parser
+            .consume_if(Token::is_string)
+            .map(|token| Expression::Text(vec![Part::Text(token.into_string())]))
+    } else if let Some(value) = parser.peek().and_then(Token::try_as_f32) {
+        parser.next();
+
+        Ok(Expression::Number(value))
+    } else {
+        parse_path(parser).map(Expression::Path)
+    }
+}
+
+fn parse_statement(parser: &mut Parser, theme: &Theme) -> Result<Statement> {
+    let name = parser.consume_if(Token::is_ident).map(Token::into_ident)?;
+
+    parse_named_statement(parser, theme, name)
+}
+
+/// Parses the rest of a `for` loop or a node whose leading identifier was already consumed.
+fn parse_named_statement(parser: &mut Parser, theme: &Theme, name: String) -> Result<Statement> {
+    if name == "for" {
+        let variable = parser.consume_if(Token::is_ident).map(Token::into_ident)?;
+
+        parser.consume(&Token::ident("in"))?;
+
+        let path = parse_path(parser)?;
+
+        parser.consume(&Token::BraceOpen)?;
+
+        let mut body = Vec::new();
+
+        while !parser.try_consume(&Token::BraceClose) {
+            body.push(parse_statement(parser, theme)?);
+        }
+
+        return Ok(Statement::For(variable, path, body));
+    }
+
+    let mut node = SourceNode {
+        element: name,
+        ..SourceNode::default()
+    };
+    let mut args = Vec::new();
+    let mut body = Vec::new();
+
+    if parser.try_consume(&Token::ParenOpen) {
+        while !parser.try_consume(&Token::ParenClose) {
+            if !args.is_empty() {
+                parser.consume(&Token::Comma)?;
+            }
+
+            args.push(parse_expression(parser)?);
+        }
+    }
+
+    if parser.try_consume(&Token::BraceOpen) {
+        while !parser.check(&Token::BraceClose) {
+            let name = parser.consume_if(Token::is_ident).map(Token::into_ident)?;
+
+            if !parser.try_consume(&Token::Colon) {
+                body.push(parse_named_statement(parser, theme, name)?);
+
+                continue;
+            }
+
+            if name == "spacing" {
+                node.spacing = Some(parser.consume_map(Token::try_as_f32)?);
+            } else if GRID_ATTRIBUTES.contains(&name.as_str()) {
+                if node.element != "Grid" {
+                    return Err(ParseError::new(format!(
+                        "{name} is only available on Grid, not {}",
+                        node.element
+                    )));
+                }
+
+                parse_grid_attribute(parser, &name, &mut node.grid)?;
+            } else {
+                parse_property(parser, &name, &mut node.style, theme)?;
+            }
+
+            if !parser.try_consume(&Token::Comma) && !parser.check(&Token::BraceClose) {
+                return Err(ParseError::new(format!(
+                    "Expected , or }} after {name}, found {}",
+                    parser
+                        .peek()
+                        .map_or_else(|| "nothing".to_string(), ToString::to_string)
+                )));
+            }
+        }
+
+        parser.consume(&Token::BraceClose)?;
+    }
+
+    Ok(Statement::Node(Box::new(node), args, body))
+}
+
+fn describe(path: &[Index]) -> String {
+    path.iter()
+        .enumerate()
+        .map(|(position, index)| match index {
+            Index::Key(key) if position == 0 => key.clone(),
+            Index::Key(key) => format!(".{key}"),
+            Index::Item(item) => format!("[{item}]"),
+        })
+        .collect()
+}
+
+fn resolve<'a>(path: &[Index], data: &'a Value, scope: &[(&str, &'a Value)]) -> Result<&'a Value> {
+    let unknown = || ParseError::new(format!("Unknown value {}", describe(path)));
+
+    let mut value = match &path[0] {
+        Index::Key(key) => scope
+            .iter()
+            .rev()
+            .find_map(|(name, value)| (name == key).then_some(*value))
+            .or_else(|| data.get(key))
+            .ok_or_else(unknown)?,
+        Index::Item(_) => return Err(unknown()),
+    };
+
+    for index in &path[1..] {
+        value = match index {
+            Index::Key(key) => value.get(key),
+            Index::Item(item) => value.get(item),
+        }
+        .ok_or_else(unknown)?;
+    }
+
+    Ok(value)
+}
+
+fn to_text(path: &[Index], value: &Value) -> Result<String> {
+    match value {
+        Value::String(value) => Ok(value.clone()),
+        Value::Number(value) => Ok(value.to_string()),
+        Value::Bool(value) => Ok(value.to_string()),
+        Value::Null => Ok(String::new()),
+        Value::Array(_) | Value::Object(_) => Err(ParseError::new(format!(
+            "{} can't be shown as text",
+            describe(path)
+        ))),
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn evaluate(expression: &Expression, data: &Value, scope: &[(&str, &Value)]) -> Result<Argument> {
+    match expression {
+        Expression::Number(value) => Ok(Argument::Number(*value)),
+        Expression::Path(path) => {
+            let value = resolve(path, data, scope)?;
+
+            value.as_f64().map_or_else(
+                || to_text(path, value).map(Argument::Text),
+                |value| Ok(Argument::Number(value as f32)),
+            )
+        }
+        Expression::Text(parts) => parts
+            .iter()
+            .map(|part| match part {
+                Part::Text(value) => Ok(value.clone()),
+                Part::Path(path) => to_text(path, resolve(path, data, scope)?),
+            })
+            .collect::<Result<String>>()
+            .map(Argument::Text),
+    }
+}
+
+fn expand<'a>(
+    statements: &'a [Statement],
+    data: &'a Value,
+    scope: &mut Vec<(&'a str, &'a Value)>,
+    nodes: &mut Vec<SourceNode>,
+) -> Result<()> {
+    for statement in statements {
+        match statement {
+            Statement::Node(node, args, body) => {
+                let mut node = SourceNode::clone(node);
+
+                node.args = args
+                    .iter()
+                    .map(|arg| evaluate(arg, data, scope))
+                    .collect::<Result<_>>()?;
+
+                expand(body, data, scope, &mut node.children)?;
+
+                nodes.push(node);
+            }
+            Statement::For(variable, path, body) => {
+                let items = resolve(path, data, scope)?
+                    .as_array()
+                    .ok_or_else(|| ParseError::new(format!("{} is not a list", describe(path))))?;
+
+                for item in items {
+                    scope.push((variable, item));
+
+                    let result = expand(body, data, scope, nodes);
+
+                    scope.pop();
+
+                    result?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses a node with its attributes and children, filling `{path}` strings and `for` loops from `data`.
+///
+/// ```text
+/// Grid {
+///     flow: row dense,
+///     column-gap: 8,
+///
+///     for c in characters.list {
+///         Text("{c.name}") { font-weight: bold }
+///     }
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns error if the node failed to parse or refers to data that doesn't exist
+pub fn parse_node(parser: &mut Parser, theme: &Theme, data: &Value) -> Result<SourceNode> {
+    let statement = parse_statement(parser, theme)?;
+
+    if matches!(statement, Statement::For(..)) {
+        return Err(ParseError::new("Expected a node, found for"));
+    }
+
+    let mut nodes = Vec::with_capacity(1);
+
+    expand(&[statement], data, &mut Vec::new(), &mut nodes)?;
+
+    Ok(nodes.remove(0))
+}
+
+/// Parses a layout made of `style { ... }` blocks followed by its root node.
+///
+/// # Errors
+///
+/// Returns error if the layout failed to parse or something follows the root node
+pub fn parse_layout(source: &str, theme: &Theme, data: &Value) -> Result<(Stylesheet, SourceNode)> {
+    let mut parser = Parser::new(Lexer::parse(source));
+    let stylesheet = parse_stylesheet(&mut parser, theme)?;
+    let node = parse_node(&mut parser, theme, data)?;
+
+    parser.peek().map_or(Ok((stylesheet, node)), |token| {
+        Err(ParseError::new(format!("Unexpected {token}")))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use muzui_styling::{
+        Color, FontWeight, FontWidth, GridFlow, TextAlign, TextDecoration, TextTransform,
+    };
+    use serde_json::{json, Value};
+
+    use super::{parse_layout, Argument, SourceNode};
+    use crate::{parser::Result, style::Theme};
+
+    fn parse(source: &str, data: &Value) -> Result<SourceNode> {
+        let theme = Theme::from([("primary".to_string(), Color::from_u32(0xFF11_2233))]);
+
+        parse_layout(source, &theme, data).map(|(_, node)| node)
+    }
+
+    #[test]
+    fn test_grid_roster() {
+        let data = json!({
+            "characters": {
+                "list": [
+                    { "name": "Amber", "level": 90 },
+                    { "name": "Kaeya", "level": 80 },
+                ]
+            }
+        });
+
+        let node = parse(
+            r#"Grid {
+                flow: row dense,
+                areas: "header header" "a b",
+                column-gap: 8,
+                padding: 4,
+
+                Text("Roster") { area: header }
+
+                for c in characters.list {
+                    Text("{c.name} Lv. {c.level}") {
+                        color: theme.primary,
+                        font-weight: bold,
+                    }
+                }
+            }"#,
+            &data,
+        )
+        .expect("failed to parse layout");
+
+        assert_eq!(node.element, "Grid");
+        assert_eq!(node.grid.flow, Some(GridFlow::RowDense));
+        assert_eq!(node.grid.column_gap, Some(8.0));
+        assert_eq!(node.grid.row_gap, None);
+        assert_eq!(node.grid.areas.as_ref().map(Vec::len), Some(2));
+        assert!(node.style.padding.is_some());
+
+        let texts = node
+            .children
+            .iter()
+            .map(|child| child.args.clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            texts,
+            vec![
+                vec![Argument::Text("Roster".to_string())],
+                vec![Argument::Text("Amber Lv. 90".to_string())],
+                vec![Argument::Text("Kaeya Lv. 80".to_string())],
+            ]
+        );
+        assert_eq!(node.children[0].style.area.as_deref(), Some("header"));
+        assert_eq!(
+            node.children[2].style.color,
+            Some(Color::from_u32(0xFF11_2233))
+        );
+        assert_eq!(node.children[2].style.font_weight, Some(FontWeight::Bold));
+    }
+
+    #[test]
+    fn test_arguments() {
+        let data = json!({ "width": 240, "icons": ["pyro.png"] });
+
+        let node = parse(r"Masonry(width) { spacing: 4, Image(icons[0]) }", &data)
+            .expect("failed to parse layout");
+
+        assert_eq!(node.args, vec![Argument::Number(240.0)]);
+        assert_eq!(node.spacing, Some(4.0));
+        assert_eq!(
+            node.children[0].args,
+            vec![Argument::Text("pyro.png".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_spans() {
+        let node = parse(
+            r#"Text {
+                Span("Lv. ") { color: theme.primary }
+                Span("{level}") { font-weight: bold }
+            }"#,
+            &json!({ "level": 90 }),
+        )
+        .expect("failed to parse layout");
+
+        assert!(node.args.is_empty());
+        assert_eq!(
+            node.children
+                .iter()
+                .map(|child| (child.element.as_str(), child.args.clone()))
+                .collect::<Vec<_>>(),
+            [
+                ("Span", vec![Argument::Text("Lv. ".to_string())]),
+                ("Span", vec![Argument::Text("90".to_string())]),
+            ]
+        );
+        assert_eq!(node.children[1].style.font_weight, Some(FontWeight::Bold));
+    }
+
+    #[test]
+    fn test_typography() {
+        let node = parse(
+            r#"Column {
+                Text("{name}") {
+                    font-weight: 350,
+                    font-width: condensed,
+                    text-align: end,
+                    line-height: 1.2,
+                    letter-spacing: 1,
+                    word-spacing: 4,
+                    text-decoration: underline,
+                    text-shadow: 0 1 theme.primary,
+                    text-transform: uppercase,
+                    max-lines: 1,
+                }
+            }"#,
+            &json!({ "name": "Amber" }),
+        )
+        .expect("failed to parse layout");
+
+        let style = &node.children[0].style;
+
+        assert_eq!(style.font_weight, Some(FontWeight::Value(350)));
+        assert_eq!(style.font_width, Some(FontWidth::Condensed));
+        assert_eq!(style.text_align, Some(TextAlign::End));
+        assert_eq!(style.line_height, Some(1.2));
+        assert_eq!(style.letter_spacing, Some(1.0));
+        assert_eq!(style.word_spacing, Some(4.0));
+        assert_eq!(style.text_decoration, Some(TextDecoration::UNDERLINE));
+        assert_eq!(style.text_shadow.as_ref().map(Vec::len), Some(1));
+        assert_eq!(style.text_transform, Some(TextTransform::Uppercase));
+        assert_eq!(style.max_lines, Some(1));
+    }
+
+    #[test]
+    fn test_invalid_layouts() {
+        let data = json!({ "characters": { "list": [{ "name": "Amber" }] } });
+
+        for source in [
+            "Column { flow: row }",
+            "Grid { for c in characters { Text(c.name) } }",
+            "Grid { for c in characters.list { Text(c.level) } }",
+            r#"Text("{missing}")"#,
+            "Column { padding: 4 color: theme.primary }",
+            "Column { width: wide }",
+            "Column {} Row {}",
+            "for c in characters.list { Text(c.name) }",
+        ] {
+            assert!(parse(source, &data).is_err(), "{source}");
+        }
+    }
+}
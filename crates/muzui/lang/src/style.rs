@@ -0,0 +1,630 @@
+use std::collections::HashMap;
+
+use muzui_styling::{
+    Color, FontFamily, FontSize, FontSlant, FontWeight, FontWidth, Length, Position, Selector,
+    Style, Stylesheet, TextAlign, TextDecoration, TextShadow, TextStroke, TextTransform, Thickness,
+    TransformOrigin, Vector,
+};
+
+use super::{
+    lexer::{Lexer, Token, Unit},
+    parser::{Parse, ParseError, Parser, Result},
+};
+
+/// Colors available to layouts as `theme.<name>`.
+pub type Theme = HashMap<String, Color>;
+
+fn parse_number(parser: &mut Parser) -> Result<f32> {
+    parser.consume_map(Token::try_as_f32)
+}
+
+fn parse_index(parser: &mut Parser) -> Result<usize> {
+    parser.consume_map(|token| token.try_as_i64().and_then(|value| value.try_into().ok()))
+}
+
+/// Parses a name given as an identifier or a string.
+fn parse_name(parser: &mut Parser) -> Result<String> {
+    parser.consume_map(|token| {
+        token
+            .try_as_ident()
+            .or_else(|| token.try_as_str())
+            .map(ToString::to_string)
+    })
+}
+
+fn parse_idents(parser: &mut Parser) -> Vec<String> {
+    std::iter::from_fn(|| parser.next_if(Token::is_ident).map(Token::into_ident)).collect()
+}
+
+/// Parses `#RRGGBB`, `#AARRGGBB` or `theme.<name>`.
+///
+/// # Errors
+///
+/// Returns error if the color is malformed or missing from `theme`
+pub fn parse_color(parser: &mut Parser, theme: &Theme) -> Result<Color> {
+    if parser.try_consume(&Token::Pound) {
+        parser.consume_map(|token| {
+            token.try_as_ident().and_then(|value| {
+                let color = u32::from_str_radix(value, 16).ok()?;
+
+                match value.len() {
+                    6 => Some(Color::from_u32(color | 0xFF00_0000)),
+                    8 => {
+                        let [alpha, red, green, blue] = color.to_be_bytes();
+
+                        Some(Color::from_rgba(red, green, blue, alpha))
+                    }
+                    _ => None,
+                }
+            })
+        })
+    } else if parser.try_consume(&Token::ident("theme")) {
+        parser.consume(&Token::Dot)?;
+
+        let name = parser.consume_if(Token::is_ident).map(Token::into_ident)?;
+
+        theme
+            .get(&name)
+            .copied()
+            .ok_or_else(|| ParseError::new(format!("Unknown theme color {name}")))
+    } else {
+        Err(ParseError::new("failed to parse color"))
+    }
+}
+
+/// Parses `none` or a list of `x y blur color` shadows, blur may be left out.
+fn parse_shadows(parser: &mut Parser, theme: &Theme) -> Result<Vec<TextShadow>> {
+    if parser.try_consume(&Token::ident("none")) {
+        return Ok(Vec::new());
+    }
+
+    let mut shadows = Vec::new();
+
+    while let Ok(x) = parse_number(parser) {
+        let y = parse_number(parser)?;
+        let blur = parse_number(parser).unwrap_or_default();
+
+        shadows.push(TextShadow::new(parse_color(parser, theme)?, x, y, blur));
+    }
+
+    if shadows.is_empty() {
+        return Err(ParseError::new("failed to parse text shadow"));
+    }
+
+    Ok(shadows)
+}
+
+impl Parse for Length {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        if parser.try_consume(&Token::ident("auto")) {
+            return Ok(Self::Auto);
+        }
+
+        if let Ok((value, unit)) = parser.consume_map(Token::try_as_dimension) {
+            return Ok(match unit {
+                Unit::Px => Self::Px(value),
+                Unit::Vw => Self::Vw(value),
+                Unit::Vh => Self::Vh(value),
+                Unit::Vmin => Self::Vmin(value),
+                Unit::Vmax => Self::Vmax(value),
+                Unit::Em => Self::Em(value),
+                Unit::Rem => Self::Rem(value),
+            });
+        }
+
+        let value = parse_number(parser)?;
+
+        Ok(if parser.try_consume(&Token::Percent) {
+            Self::Percent(value)
+        } else {
+            Self::Px(value)
+        })
+    }
+}
+
+impl Parse for Thickness {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        let values = std::iter::from_fn(|| parse_number(parser).ok())
+            .take(4)
+            .collect::<Vec<_>>();
+
+        match values[..] {
+            [value] => Ok(value.into()),
+            [vertical, horizontal] => Ok([vertical, horizontal].into()),
+            [left, top, right, bottom] => Ok([left, top, right, bottom].into()),
+            _ => Err(ParseError::new("failed to parse thickness")),
+        }
+    }
+}
+
+impl Parse for Vector {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        let x = parse_number(parser)?;
+
+        Ok(parse_number(parser).map_or_else(|_| x.into(), |y| Self::new(x, y)))
+    }
+}
+
+impl Parse for TransformOrigin {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        Ok(Self::new(Length::parse(parser)?, Length::parse(parser)?))
+    }
+}
+
+impl Parse for FontWeight {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        parser.consume_map(|token| {
+            token
+                .try_as_ident()
+                .and_then(|ident| match ident {
+                    "thin" => Some(Self::Thin),
+                    "extra-light" => Some(Self::ExtraLight),
+                    "light" => Some(Self::Light),
+                    "normal" => Some(Self::Normal),
+                    "medium" => Some(Self::Medium),
+                    "semi-bold" => Some(Self::SemiBold),
+                    "bold" => Some(Self::Bold),
+                    "extra-bold" => Some(Self::ExtraBold),
+                    "black" => Some(Self::Black),
+                    _ => None,
+                })
+                .or_else(|| {
+                    token
+                        .try_as_i64()
+                        .and_then(|value| u16::try_from(value).ok())
+                        .filter(|value| (1..=1000).contains(value))
+                        .map(Self::Value)
+                })
+        })
+    }
+}
+
+impl Parse for FontWidth {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        parser.consume_map(|token| {
+            token.try_as_ident().and_then(|ident| match ident {
+                "ultra-condensed" => Some(Self::UltraCondensed),
+                "extra-condensed" => Some(Self::ExtraCondensed),
+                "condensed" => Some(Self::Condensed),
+                "semi-condensed" => Some(Self::SemiCondensed),
+                "normal" => Some(Self::Normal),
+                "semi-expanded" => Some(Self::SemiExpanded),
+                "expanded" => Some(Self::Expanded),
+                "extra-expanded" => Some(Self::ExtraExpanded),
+                "ultra-expanded" => Some(Self::UltraExpanded),
+                _ => None,
+            })
+        })
+    }
+}
+
+impl Parse for TextAlign {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        parser.consume_map(|token| {
+            token.try_as_ident().and_then(|ident| match ident {
+                "left" => Some(Self::Left),
+                "right" => Some(Self::Right),
+                "center" => Some(Self::Center),
+                "justify" => Some(Self::Justify),
+                "start" => Some(Self::Start),
+                "end" => Some(Self::End),
+                _ => None,
+            })
+        })
+    }
+}
+
+impl Parse for TextTransform {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        parser.consume_map(|token| {
+            token.try_as_ident().and_then(|ident| match ident {
+                "none" => Some(Self::None),
+                "uppercase" => Some(Self::Uppercase),
+                "lowercase" => Some(Self::Lowercase),
+                "capitalize" => Some(Self::Capitalize),
+                _ => None,
+            })
+        })
+    }
+}
+
+/// Parses `none` or a list of lines, e.g. `underline line-through`.
+impl Parse for TextDecoration {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        if parser.try_consume(&Token::ident("none")) {
+            return Ok(Self::NONE);
+        }
+
+        let mut decoration = parser.consume_map(parse_decoration_line)?;
+
+        while let Ok(line) = parser.consume_map(parse_decoration_line) {
+            decoration = decoration.with(line);
+        }
+
+        Ok(decoration)
+    }
+}
+
+fn parse_decoration_line(token: &Token) -> Option<TextDecoration> {
+    token.try_as_ident().and_then(|ident| match ident {
+        "underline" => Some(TextDecoration::UNDERLINE),
+        "overline" => Some(TextDecoration::OVERLINE),
+        "line-through" => Some(TextDecoration::LINE_THROUGH),
+        _ => None,
+    })
+}
+
+impl Parse for FontSlant {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        parser.consume_map(|token| {
+            token.try_as_ident().and_then(|ident| match ident {
+                "upright" => Some(Self::Upright),
+                "italic" => Some(Self::Italic),
+                "oblique" => Some(Self::Oblique),
+                _ => None,
+            })
+        })
+    }
+}
+
+impl Parse for FontSize {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        parse_number(parser).map(Self::new)
+    }
+}
+
+impl Parse for FontFamily {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        String::parse(parser).map(Self::new)
+    }
+}
+
+impl Parse for Position {
+    fn parse(parser: &mut Parser) -> Result<Self> {
+        parser.consume_map(|token| {
+            token.try_as_ident().and_then(|ident| match ident {
+                "absolute" => Some(Self::Absolute),
+                "relative" => Some(Self::Relative),
+                _ => None,
+            })
+        })
+    }
+}
+
+/// Parses the value of the `name` property into `style`.
+///
+/// `class` and `state` take a list of names separated by whitespace, e.g. `class: card primary`.
+///
+/// # Errors
+///
+/// Returns error if the property is unknown or its value failed to parse
+pub fn parse_property(
+    parser: &mut Parser,
+    name: &str,
+    style: &mut Style,
+    theme: &Theme,
+) -> Result<()> {
+    match name {
+        "margin" => style.margin = Some(Thickness::parse(parser)?),
+        "padding" => style.padding = Some(Thickness::parse(parser)?),
+        "corner-radius" => style.corner_radius = Some(Thickness::parse(parser)?),
+        "row" => style.row = Some(parse_index(parser)?),
+        "row-span" => style.row_span = Some(parse_index(parser)?),
+        "column" => style.column = Some(parse_index(parser)?),
+        "column-span" => style.column_span = Some(parse_index(parser)?),
+        "area" => style.area = Some(parse_name(parser)?),
+        "width" => style.width = Some(Length::parse(parser)?),
+        "height" => style.height = Some(Length::parse(parser)?),
+        "background" => style.background = Some(parse_color(parser, theme)?),
+        "color" => style.color = Some(parse_color(parser, theme)?),
+        "font-family" => style.font_family = Some(FontFamily::parse(parser)?),
+        "font-weight" => style.font_weight = Some(FontWeight::parse(parser)?),
+        "font-slant" => style.font_slant = Some(FontSlant::parse(parser)?),
+        "font-size" => style.font_size = Some(FontSize::parse(parser)?),
+        "font-width" => style.font_width = Some(FontWidth::parse(parser)?),
+        "text-align" => style.text_align = Some(TextAlign::parse(parser)?),
+        "line-height" => style.line_height = Some(parse_number(parser)?),
+        "letter-spacing" => style.letter_spacing = Some(parse_number(parser)?),
+        "word-spacing" => style.word_spacing = Some(parse_number(parser)?),
+        "text-decoration" => style.text_decoration = Some(TextDecoration::parse(parser)?),
+        "text-transform" => style.text_transform = Some(TextTransform::parse(parser)?),
+        "text-shadow" => style.text_shadow = Some(parse_shadows(parser, theme)?),
+        "text-stroke" => {
+            style.text_stroke = Some(TextStroke::new(
+                parse_number(parser)?,
+                parse_color(parser, theme)?,
+            ));
+        }
+        "max-lines" => style.max_lines = Some(parse_index(parser)?),
+        "position" => style.position = Some(Position::parse(parser)?),
+        "x" => style.x = Some(parse_number(parser)?),
+        "y" => style.y = Some(parse_number(parser)?),
+        "translate" => style.translate = Some(Vector::parse(parser)?),
+        "rotate" => style.rotate = Some(parse_number(parser)?),
+        "scale" => style.scale = Some(Vector::parse(parser)?),
+        "skew" => style.skew = Some(Vector::parse(parser)?),
+        "transform-origin" => style.transform_origin = Some(TransformOrigin::parse(parser)?),
+        "id" => style.id = Some(parse_name(parser)?),
+        "key" => style.key = Some(parse_name(parser)?),
+        "class" => style.classes.extend(parse_idents(parser)),
+        "state" => style.states.extend(parse_idents(parser)),
+        name => return Err(ParseError::new(format!("Unknown style property {name}"))),
+    }
+
+    Ok(())
+}
+
+/// Parses `Element.class:state`, `*` stands for any element.
+///
+/// # Errors
+///
+/// Returns error if there is no selector
+pub fn parse_selector(parser: &mut Parser) -> Result<Selector> {
+    let mut selector = Selector::new();
+
+    let any = parser.try_consume(&Token::Star);
+
+    if !any {
+        selector.element = parser.next_if(Token::is_ident).map(Token::into_ident);
+    }
+
+    loop {
+        if parser.try_consume(&Token::Dot) {
+            selector
+                .classes
+                .push(parser.consume_if(Token::is_ident).map(Token::into_ident)?);
+        } else if parser.try_consume(&Token::Colon) {
+            selector
+                .states
+                .push(parser.consume_if(Token::is_ident).map(Token::into_ident)?);
+        } else {
+            break;
+        }
+    }
+
+    if !any && selector == Selector::new() {
+        return Err(ParseError::new(format!(
+            "Expected selector, found {}",
+            parser
+                .peek()
+                .map_or_else(|| "nothing".to_string(), ToString::to_string)
+        )));
+    }
+
+    Ok(selector)
+}
+
+/// Parses a `{ name: value, ... }` block of properties.
+///
+/// # Errors
+///
+/// Returns error if any of the properties failed to parse
+pub fn parse_declarations(parser: &mut Parser, theme: &Theme) -> Result<Style> {
+    let mut style = Style::new();
+
+    parser.consume(&Token::BraceOpen)?;
+
+    while !parser.try_consume(&Token::BraceClose) {
+        let name = parser.consume_if(Token::is_ident).map(Token::into_ident)?;
+
+        parser.consume(&Token::Colon)?;
+
+        parse_property(parser, &name, &mut style, theme)?;
+
+        if !parser.try_consume(&Token::Comma) {
+            parser.consume(&Token::BraceClose)?;
+
+            break;
+        }
+    }
+
+    Ok(style)
+}
+
+/// Parses the `style { ... }` blocks at the start of a layout, leaving the parser at the root node.
+///
+/// ```text
+/// style {
+///     .card { padding: 8, background: theme.surface_container }
+///     Text.title:first-child { font-weight: bold }
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns error if any of the rules failed to parse
+pub fn parse_stylesheet(parser: &mut Parser, theme: &Theme) -> Result<Stylesheet> {
+    let mut stylesheet = Stylesheet::new();
+
+    while parser.try_consume(&Token::ident("style")) {
+        parser.consume(&Token::BraceOpen)?;
+
+        while !parser.try_consume(&Token::BraceClose) {
+            let selector = parse_selector(parser)?;
+
+            stylesheet.add(selector, parse_declarations(parser, theme)?);
+        }
+    }
+
+    Ok(stylesheet)
+}
+
+/// Parses a source made only of `style { ... }` blocks, e.g. a stylesheet shared by layouts.
+///
+/// # Errors
+///
+/// Returns error if any of the rules failed to parse or something other than a block follows
+pub fn parse_stylesheet_source(source: &str, theme: &Theme) -> Result<Stylesheet> {
+    let mut parser = Parser::new(Lexer::parse(source));
+    let stylesheet = parse_stylesheet(&mut parser, theme)?;
+
+    parser.peek().map_or(Ok(stylesheet), |token| {
+        Err(ParseError::new(format!("Unexpected {token}")))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use muzui_styling::{
+        Color, FontWeight, FontWidth, Length, Selector, TextAlign, TextDecoration, TextShadow,
+        TextStroke, TextTransform, TransformOrigin, Vector,
+    };
+
+    use super::{parse_stylesheet, parse_stylesheet_source, Theme};
+    use crate::{
+        lexer::{Lexer, Token},
+        parser::Parser,
+    };
+
+    #[test]
+    fn test_stylesheet() {
+        let mut parser = Parser::new(Lexer::parse(
+            "style {
+                .card { padding: 8 16, background: theme.surface, }
+                Text.title:first-child { font-size: 32, font-weight: bold, width: 50% }
+                * { color: #80FF0000 }
+            }
+
+            Column { class: card primary }",
+        ));
+        let theme = Theme::from([("surface".to_string(), Color::from_rgb(1, 2, 3))]);
+
+        let stylesheet = parse_stylesheet(&mut parser, &theme).expect("failed to parse stylesheet");
+
+        assert_eq!(stylesheet.rules.len(), 3);
+        assert_eq!(stylesheet.rules[0].selector, Selector::new().class("card"));
+        assert_eq!(stylesheet.rules[0].style.padding, Some([8.0, 16.0].into()));
+        assert_eq!(
+            stylesheet.rules[0].style.background,
+            Some(Color::from_rgb(1, 2, 3))
+        );
+        assert_eq!(
+            stylesheet.rules[1].selector,
+            Selector::parse("Text.title:first-child")
+        );
+        assert_eq!(stylesheet.rules[1].style.width, Some(Length::Percent(50.0)));
+        assert_eq!(stylesheet.rules[2].selector, Selector::new());
+        assert_eq!(
+            stylesheet.rules[2].style.color,
+            Some(Color::from_rgba(255, 0, 0, 0x80))
+        );
+
+        assert_eq!(parser.next(), Some(Token::ident("Column")));
+    }
+
+    #[test]
+    fn test_typography() {
+        let mut parser = Parser::new(Lexer::parse(
+            "style {
+                Text {
+                    font-weight: 350,
+                    font-width: condensed,
+                    text-align: center,
+                    line-height: 1.5,
+                    letter-spacing: 2,
+                    text-decoration: underline line-through,
+                    text-transform: uppercase,
+                    max-lines: 2,
+                    text-shadow: 0 2 4 #80000000 1 1 theme.surface,
+                    text-stroke: 2 #000000,
+                }
+            }",
+        ));
+
+        let theme = Theme::from([("surface".to_string(), Color::from_rgb(1, 2, 3))]);
+
+        let stylesheet = parse_stylesheet(&mut parser, &theme).expect("failed to parse stylesheet");
+        let style = &stylesheet.rules[0].style;
+
+        assert_eq!(style.font_weight, Some(FontWeight::Value(350)));
+        assert_eq!(style.font_width, Some(FontWidth::Condensed));
+        assert_eq!(style.text_align, Some(TextAlign::Center));
+        assert_eq!(style.line_height, Some(1.5));
+        assert_eq!(style.letter_spacing, Some(2.0));
+        assert_eq!(
+            style.text_decoration,
+            Some(TextDecoration::UNDERLINE.with(TextDecoration::LINE_THROUGH))
+        );
+        assert_eq!(style.text_transform, Some(TextTransform::Uppercase));
+        assert_eq!(style.max_lines, Some(2));
+        assert_eq!(
+            style.text_shadow.as_deref(),
+            Some(
+                &[
+                    TextShadow::new(Color::from_rgba(0, 0, 0, 0x80), 0.0, 2.0, 4.0),
+                    TextShadow::new(Color::from_rgb(1, 2, 3), 1.0, 1.0, 0.0),
+                ][..]
+            )
+        );
+        assert_eq!(
+            style.text_stroke,
+            Some(TextStroke::new(2.0, Color::from_rgb(0, 0, 0)))
+        );
+    }
+
+    #[test]
+    fn test_transforms() {
+        let mut parser = Parser::new(Lexer::parse(
+            "style {
+                Row.badge {
+                    translate: 4 -2,
+                    rotate: -15,
+                    scale: 1.5,
+                    skew: 10 0,
+                    transform-origin: 0% 100%,
+                }
+            }",
+        ));
+
+        let stylesheet =
+            parse_stylesheet(&mut parser, &Theme::new()).expect("failed to parse stylesheet");
+        let style = &stylesheet.rules[0].style;
+
+        assert_eq!(style.translate, Some(Vector::new(4.0, -2.0)));
+        assert_eq!(style.rotate, Some(-15.0));
+        assert_eq!(style.scale, Some(Vector::new(1.5, 1.5)));
+        assert_eq!(style.skew, Some(Vector::new(10.0, 0.0)));
+        assert_eq!(
+            style.transform_origin,
+            Some(TransformOrigin::new(
+                Length::Percent(0.0),
+                Length::Percent(100.0)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_invalid_stylesheet() {
+        let theme = Theme::new();
+
+        for source in [
+            "style { .card { padding: 8 } ",
+            "style { { padding: 8 } }",
+            "style { .card { gap: 8 } }",
+            "style { .card { background: theme.missing } }",
+        ] {
+            let mut parser = Parser::new(Lexer::parse(source));
+
+            assert!(parse_stylesheet(&mut parser, &theme).is_err(), "{source}");
+        }
+
+        let mut parser = Parser::new(Lexer::parse("Column {}"));
+
+        assert!(parse_stylesheet(&mut parser, &theme).is_ok_and(|stylesheet| stylesheet.is_empty()));
+        assert_eq!(parser.next(), Some(Token::ident("Column")));
+    }
+
+    #[test]
+    fn test_stylesheet_source() {
+        let theme = Theme::new();
+
+        let stylesheet = parse_stylesheet_source(
+            "style { .card { padding: 8 } } style { Text { color: #FF0000 } }",
+            &theme,
+        )
+        .expect("failed to parse stylesheet");
+
+        assert_eq!(stylesheet.rules.len(), 2);
+        assert!(parse_stylesheet_source("style { .card { padding: 8 } } Column", &theme).is_err());
+    }
+}
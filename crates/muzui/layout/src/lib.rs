@@ -1,39 +1,111 @@
 #![warn(clippy::all, clippy::pedantic, clippy::nursery)]
 
-use muzui_styling::{Length, Operation, Style};
+use muzui_styling::{FontSize, Length, Operation, Style};
+use serde::Serialize;
 
 pub type Point = muzui_geometry::Point<f32>;
 pub type Rect = muzui_geometry::Rect<f32>;
 pub type Size = muzui_geometry::Size<f32>;
 
-fn eval_length(length: &Length, available_space: f32, available: Size) -> f32 {
-    match length {
-        Length::Auto => 0.0,
-        Length::Px(value) => *value,
-        Length::Percent(value) => available_space * (*value / 100.0),
-        Length::ParentWidth => available.width,
-        Length::ParentHeight => available.height,
-        Length::Dynamic(operations) => {
-            operations.iter().fold(0.0, |_, operation| match operation {
-                Operation::Add(a, b) => {
-                    eval_length(a, available_space, available)
-                        + eval_length(b, available_space, available)
-                }
-                Operation::Sub(a, b) => {
-                    eval_length(a, available_space, available)
-                        - eval_length(b, available_space, available)
-                }
-                Operation::Mul(a, b) => {
-                    eval_length(a, available_space, available)
-                        * eval_length(b, available_space, available)
-                }
-                Operation::Div(a, b) => {
-                    eval_length(a, available_space, available)
-                        / eval_length(b, available_space, available)
-                }
-            })
+/// Values of the render target that lengths can refer to.
+pub trait Environment {
+    fn viewport(&self) -> Size;
+
+    /// Font size of the root node, which `rem` lengths are relative to.
+    fn root_font_size(&self) -> f32 {
+        FontSize::default().size
+    }
+}
+
+fn definite(value: f32) -> Option<f32> {
+    value.is_finite().then_some(value)
+}
+
+/// Everything a [`Length`] can be resolved against.
+///
+/// Sizes that aren't known yet are infinite; lengths referring to them resolve to `None`
+/// and the node falls back to its content size.
+#[derive(Debug, Clone, Copy)]
+pub struct LengthContext {
+    /// Size of the space given by the parent, used by percentages and parent references.
+    pub parent: Size,
+    pub viewport: Size,
+    pub font_size: f32,
+    pub root_font_size: f32,
+    /// Size of the node itself.
+    pub size: Size,
+}
+
+impl LengthContext {
+    #[must_use]
+    pub fn new<E: Environment>(environment: &E, style: &Style, constraints: Constraints) -> Self {
+        Self {
+            parent: constraints.max,
+            viewport: environment.viewport(),
+            font_size: style.font_size.unwrap_or_default().size,
+            root_font_size: environment.root_font_size(),
+            size: Size::new(f32::INFINITY, f32::INFINITY),
+        }
+    }
+
+    #[must_use]
+    pub const fn with_size(self, size: Size) -> Self {
+        Self { size, ..self }
+    }
+
+    /// Resolves a length along the axis whose parent size is `basis`.
+    #[must_use]
+    pub fn resolve(&self, length: &Length, basis: f32) -> Option<f32> {
+        match length {
+            Length::Auto => None,
+            Length::Px(value) => Some(*value),
+            Length::Percent(value) => definite(basis).map(|basis| basis * (*value / 100.0)),
+            Length::Vw(value) => definite(self.viewport.width * (*value / 100.0)),
+            Length::Vh(value) => definite(self.viewport.height * (*value / 100.0)),
+            Length::Vmin(value) => {
+                definite(self.viewport.width.min(self.viewport.height) * (*value / 100.0))
+            }
+            Length::Vmax(value) => {
+                definite(self.viewport.width.max(self.viewport.height) * (*value / 100.0))
+            }
+            Length::Em(value) => definite(self.font_size * *value),
+            Length::Rem(value) => definite(self.root_font_size * *value),
+            Length::Width => definite(self.size.width),
+            Length::Height => definite(self.size.height),
+            Length::ParentWidth => definite(self.parent.width),
+            Length::ParentHeight => definite(self.parent.height),
+            Length::ViewportWidth => definite(self.viewport.width),
+            Length::ViewportHeight => definite(self.viewport.height),
+            Length::FontSize => definite(self.font_size),
+            Length::Dynamic(operation) => self.evaluate(operation, basis).and_then(definite),
         }
     }
+
+    #[must_use]
+    pub fn resolve_width(&self, length: &Length) -> Option<f32> {
+        self.resolve(length, self.parent.width)
+    }
+
+    #[must_use]
+    pub fn resolve_height(&self, length: &Length) -> Option<f32> {
+        self.resolve(length, self.parent.height)
+    }
+
+    fn evaluate(&self, operation: &Operation, basis: f32) -> Option<f32> {
+        let resolve = |length| self.resolve(length, basis);
+
+        Some(match operation {
+            Operation::Add(a, b) => resolve(a)? + resolve(b)?,
+            Operation::Sub(a, b) => resolve(a)? - resolve(b)?,
+            Operation::Mul(a, b) => resolve(a)? * resolve(b)?,
+            Operation::Div(a, b) => resolve(a)? / resolve(b)?,
+            Operation::Min(a, b) => resolve(a)?.min(resolve(b)?),
+            Operation::Max(a, b) => resolve(a)?.max(resolve(b)?),
+            Operation::Clamp(min, value, max) => {
+                resolve(value)?.min(resolve(max)?).max(resolve(min)?)
+            }
+        })
+    }
 }
 
 // impl Parse for Length {
@@ -48,53 +120,128 @@ fn eval_length(length: &Length, available_space: f32, available: Size) -> f32 {
 //     }
 // }
 
-#[derive(Debug, Clone)]
+/// Minimum and maximum sizes a node is allowed to take.
+///
+/// Constraints are passed down the tree while measuring, sizes are passed
+/// back up. An unbounded axis has an infinite maximum.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Constraints {
+    pub min: Size,
+    pub max: Size,
+}
+
+impl Constraints {
+    pub const UNBOUNDED: Self = Self::loose(Size::new(f32::INFINITY, f32::INFINITY));
+
+    #[must_use]
+    pub const fn new(min: Size, max: Size) -> Self {
+        Self { min, max }
+    }
+
+    /// Constraints that only allow exactly `size`.
+    #[must_use]
+    pub const fn tight(size: Size) -> Self {
+        Self::new(size, size)
+    }
+
+    /// Constraints that allow any size up to `max`.
+    #[must_use]
+    pub const fn loose(max: Size) -> Self {
+        Self::new(Size::new(0.0, 0.0), max)
+    }
+
+    #[must_use]
+    pub const fn has_bounded_width(&self) -> bool {
+        self.max.width.is_finite()
+    }
+
+    #[must_use]
+    pub const fn has_bounded_height(&self) -> bool {
+        self.max.height.is_finite()
+    }
+
+    #[must_use]
+    pub const fn loosen(&self) -> Self {
+        Self::loose(self.max)
+    }
+
+    /// Shrinks both bounds by `size`, never going below zero.
+    #[must_use]
+    pub fn deflate(&self, size: Size) -> Self {
+        Self::new(
+            Size::new(
+                (self.min.width - size.width).max(0.0),
+                (self.min.height - size.height).max(0.0),
+            ),
+            Size::new(
+                (self.max.width - size.width).max(0.0),
+                (self.max.height - size.height).max(0.0),
+            ),
+        )
+    }
+
+    /// Returns the size closest to `size` that satisfies the constraints.
+    #[must_use]
+    pub const fn constrain(&self, size: Size) -> Size {
+        Size::new(
+            size.width.min(self.max.width).max(self.min.width),
+            size.height.min(self.max.height).max(self.min.height),
+        )
+    }
+}
+
+/// The kind of content size asked from [`Measurer::intrinsic_size`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sizing {
+    /// The smallest size the content can take without overflowing, e.g. the longest word of a text.
+    MinContent,
+    /// The size the content takes when nothing limits it, e.g. a text laid out on a single line.
+    MaxContent,
+}
+
+/// The result of measuring a node.
+///
+/// `outer` is the border box of the node and `inner` is its content box.
+/// While measuring, rects are relative to the border box of the parent.
+/// [`MeasureNode::arrange`] turns them into absolute coordinates.
+#[derive(Debug, Clone, Serialize)]
 pub struct MeasureNode {
     pub outer: Rect,
     pub inner: Rect,
-    pub children: Vec<MeasureNode>,
+    pub children: Vec<Self>,
 }
 
 impl MeasureNode {
+    /// Sizes a node from its style, falling back to `content` (the size of the content box)
+    /// for every axis without an explicit length.
+    ///
+    /// The node is placed at the origin; its parent positions it with [`MeasureNode::set_position`].
     #[must_use]
-    pub fn new(
-        Style {
-            margin,
-            padding,
-            width,
-            height,
-            position,
-            x,
-            y,
-            ..
-        }: &Style,
-        parent: &Rect,
-        size: Size,
+    pub fn new<E: Environment>(
+        environment: &E,
+        style: &Style,
+        constraints: Constraints,
+        content: Size,
     ) -> Self {
-        let width = width
-            .as_ref()
-            .map(|width| eval_length(width, parent.size.width, parent.size));
-        let height = height
-            .as_ref()
-            .map(|height| eval_length(height, parent.size.height, parent.size));
-
-        let outer = Rect::new(
-            if position.is_absolute() {
-                Point::new(
-                    x.unwrap_or_default() + margin.left,
-                    y.unwrap_or_default() + margin.top,
-                )
-            } else {
-                Point::new(parent.origin.x + margin.left, parent.origin.y + margin.top)
-            },
-            Size::new(width.unwrap_or(size.width), height.unwrap_or(size.height)),
+        let padding = style.padding.unwrap_or_default();
+
+        let padding_size = Size::new(padding.left + padding.right, padding.top + padding.bottom);
+        let fallback = constraints.constrain(content + padding_size);
+
+        let (width, height) = Self::resolve_size(environment, style, constraints, fallback);
+
+        let size = Size::new(
+            width.map_or(fallback.width, |width| width.max(constraints.min.width)),
+            height.map_or(fallback.height, |height| height.max(constraints.min.height)),
         );
 
+        let outer = Rect::new(Point::default(), size);
+
         let inner = Rect::new(
-            Point::new(outer.origin.x + padding.left, outer.origin.y + padding.top),
+            Point::new(padding.left, padding.top),
             Size::new(
-                outer.size.width - padding.left - padding.right,
-                outer.size.height - padding.top - padding.bottom,
+                (size.width - padding_size.width).max(0.0),
+                (size.height - padding_size.height).max(0.0),
             ),
         );
 
@@ -105,85 +252,123 @@ impl MeasureNode {
         }
     }
 
+    /// Resolves the explicit width and height of the border box, if the style has ones that can be resolved.
+    ///
+    /// References to the node's own size use `fallback` for the axis that isn't explicit.
     #[must_use]
-    pub fn from_parent(
-        Style {
-            margin,
-            padding,
-            width,
-            height,
-            ..
-        }: &Style,
-        parent: &Rect,
-    ) -> Self {
-        let width = width
-            .as_ref()
-            .map(|width| eval_length(width, parent.size.width, parent.size));
-        let height = height
-            .as_ref()
-            .map(|height| eval_length(height, parent.size.height, parent.size));
-
-        let outer = Rect::new(
-            Point::new(parent.origin.x + margin.left, parent.origin.y + margin.top),
-            Size::new(
-                width.unwrap_or(parent.size.width - margin.right),
-                height.unwrap_or(parent.size.height - margin.bottom),
-            ),
-        );
+    pub fn resolve_size<E: Environment>(
+        environment: &E,
+        style: &Style,
+        constraints: Constraints,
+        fallback: Size,
+    ) -> (Option<f32>, Option<f32>) {
+        let lengths = LengthContext::new(environment, style, constraints);
 
-        let inner = Rect::new(
-            Point::new(outer.origin.x + padding.left, outer.origin.y + padding.top),
-            Size::new(
-                outer.size.width - padding.left - padding.right,
-                outer.size.height - padding.top - padding.bottom,
-            ),
-        );
+        let resolve_width = |height: f32| {
+            style.width.as_ref().and_then(|width| {
+                lengths
+                    .with_size(Size::new(f32::INFINITY, height))
+                    .resolve_width(width)
+            })
+        };
 
-        Self {
-            outer,
-            inner,
-            children: Vec::new(),
-        }
-    }
+        let width = resolve_width(f32::INFINITY);
+        let height = style.height.as_ref().and_then(|height| {
+            lengths
+                .with_size(Size::new(width.unwrap_or(fallback.width), f32::INFINITY))
+                .resolve_height(height)
+        });
 
-    pub fn set_y(&mut self, style: &Style, y: f32) {
-        self.outer.origin.y = y + style.margin.top;
-        self.inner.origin.y = y + style.padding.top;
-    }
+        // The width may depend on the height, which is only known now.
+        let width = width.or_else(|| resolve_width(height.unwrap_or(fallback.height)));
 
-    pub fn set_x(&mut self, style: &Style, x: f32) {
-        self.outer.origin.x = x + style.margin.left;
-        self.inner.origin.x = x + style.padding.left;
+        (width, height)
     }
 
-    pub fn set_height(&mut self, style: &Style, height: f32) {
-        self.outer.size.height = height + style.padding.top + style.padding.bottom;
-        self.inner.size.height = height;
-    }
+    /// Constraints for the children of a node, i.e. the space left inside its content box.
+    ///
+    /// Axes with an explicit length are bounded by it, other axes inherit the parent's bounds.
+    #[must_use]
+    pub fn content_constraints<E: Environment>(
+        environment: &E,
+        style: &Style,
+        constraints: Constraints,
+    ) -> Constraints {
+        let padding = style.padding.unwrap_or_default();
 
-    pub fn set_width(&mut self, style: &Style, width: f32) {
-        self.outer.size.width = width + style.padding.left + style.padding.right;
-        self.inner.size.width = width;
+        let (width, height) = Self::resolve_size(environment, style, constraints, constraints.max);
+
+        let max = Size::new(
+            width.unwrap_or(constraints.max.width),
+            height.unwrap_or(constraints.max.height),
+        );
+
+        Constraints::loose(max).deflate(Size::new(
+            padding.left + padding.right,
+            padding.top + padding.bottom,
+        ))
     }
 
+    /// The size of the border box including the margin.
     #[must_use]
-    pub fn offset(&self, point: Point) -> Rect {
-        let mut inner = self.inner;
+    pub fn margin_size(&self, style: &Style) -> Size {
+        let margin = style.margin.unwrap_or_default();
+
+        Size::new(
+            self.outer.size.width + margin.left + margin.right,
+            self.outer.size.height + margin.top + margin.bottom,
+        )
+    }
 
-        inner += point;
-        inner -= Size::from(point);
+    /// Moves the node so its margin box starts at `point`, relative to the parent.
+    pub fn set_position(&mut self, style: &Style, point: Point) {
+        let margin = style.margin.unwrap_or_default();
+        let origin = Point::new(point.x + margin.left, point.y + margin.top);
+        let delta = origin - self.outer.origin;
 
-        inner
+        self.outer += delta;
+        self.inner += delta;
+    }
+
+    /// Converts the relative rects produced while measuring into absolute ones,
+    /// with the parent's border box starting at `origin`.
+    pub fn arrange(&mut self, origin: Point) {
+        self.outer += origin;
+        self.inner += origin;
+
+        for child in &mut self.children {
+            child.arrange(self.outer.origin);
+        }
     }
 }
 
 pub trait Measurer<Context> {
-    fn measure(&self, context: &Context, parent: &Rect) -> MeasureNode;
+    fn measure(&self, context: &Context, constraints: Constraints) -> MeasureNode;
     fn get_style(&self) -> &Style;
+
+    /// Returns the border box size of the node for the given kind of content size.
+    fn intrinsic_size(&self, context: &Context, sizing: Sizing) -> Size {
+        let constraints = match sizing {
+            Sizing::MinContent => Constraints::loose(Size::new(0.0, f32::INFINITY)),
+            Sizing::MaxContent => Constraints::UNBOUNDED,
+        };
+
+        self.measure(context, constraints).outer.size
+    }
+
+    /// Measures the node inside `bounds` and positions the whole tree in absolute coordinates.
+    fn layout(&self, context: &Context, bounds: Rect) -> MeasureNode {
+        let mut node = self.measure(context, Constraints::loose(bounds.size));
+
+        node.set_position(self.get_style(), Point::default());
+        node.arrange(bounds.origin);
+
+        node
+    }
 }
 
 pub trait Layout<Context> {
-    fn measure(&self, context: &Context, style: &Style, parent: &Rect) -> MeasureNode;
+    fn measure(&self, context: &Context, style: &Style, constraints: Constraints) -> MeasureNode;
 }
 
 // impl Parse for Orientation {
@@ -197,3 +382,188 @@ pub trait Layout<Context> {
 //         })
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use muzui_styling::{
+        clamp, em, font_size, height, max, min, parent_width, percent, px, rem, vh, viewport_width,
+        vmax, vmin, vw, width, Length, Operation, Style,
+    };
+
+    use super::{Constraints, Environment, LengthContext, MeasureNode, Point, Rect, Size};
+
+    struct Viewport;
+
+    impl Environment for Viewport {
+        fn viewport(&self) -> Size {
+            Size::new(1920.0, 1080.0)
+        }
+    }
+
+    fn lengths(parent: Size) -> LengthContext {
+        LengthContext::new(
+            &Viewport,
+            &Style::builder().font_size(16.0).build(),
+            Constraints::loose(parent),
+        )
+    }
+
+    #[test]
+    fn test_percent_of_available_space() {
+        let style = Style::builder().width(percent(50.0)).padding(10.0).build();
+        let node = MeasureNode::new(
+            &Viewport,
+            &style,
+            Constraints::loose(Size::new(200.0, 100.0)),
+            Size::new(20.0, 20.0),
+        );
+
+        assert_eq!(node.outer, Rect::from_xywh(0.0, 0.0, 100.0, 40.0));
+        assert_eq!(node.inner, Rect::from_xywh(10.0, 10.0, 80.0, 20.0));
+    }
+
+    #[test]
+    fn test_percent_without_bounds_is_auto() {
+        let style = Style::builder().width(percent(50.0)).build();
+        let node = MeasureNode::new(
+            &Viewport,
+            &style,
+            Constraints::UNBOUNDED,
+            Size::new(20.0, 20.0),
+        );
+
+        assert_eq!(node.outer.size, Size::new(20.0, 20.0));
+    }
+
+    #[test]
+    fn test_tight_constraints() {
+        let style = Style::builder().width(px(50.0)).build();
+        let node = MeasureNode::new(
+            &Viewport,
+            &style,
+            Constraints::tight(Size::new(80.0, 10.0)),
+            Size::new(20.0, 20.0),
+        );
+
+        assert_eq!(node.outer.size, Size::new(80.0, 10.0));
+    }
+
+    #[test]
+    fn test_arrange() {
+        let style = Style::builder().margin(5.0).build();
+
+        let mut child = MeasureNode::new(
+            &Viewport,
+            &style,
+            Constraints::UNBOUNDED,
+            Size::new(10.0, 10.0),
+        );
+
+        child.set_position(&style, Point::new(20.0, 0.0));
+
+        let mut root = MeasureNode::new(
+            &Viewport,
+            &Style::new(),
+            Constraints::UNBOUNDED,
+            Size::new(50.0, 50.0),
+        );
+
+        root.children.push(child);
+        root.arrange(Point::new(100.0, 100.0));
+
+        assert_eq!(root.outer.origin, Point::new(100.0, 100.0));
+        assert_eq!(
+            root.children[0].outer,
+            Rect::from_xywh(125.0, 105.0, 10.0, 10.0)
+        );
+    }
+
+    #[test]
+    fn test_nested_operations() {
+        let lengths = lengths(Size::new(200.0, 100.0));
+
+        // (100% - 16) / 2 + 1em
+        let length = (percent(100.0) - 16.0) / 2.0 + font_size();
+
+        assert_eq!(lengths.resolve_width(&length), Some(108.0));
+        assert_eq!(lengths.resolve_height(&length), Some(58.0));
+    }
+
+    #[test]
+    fn test_min_max_clamp() {
+        let lengths = lengths(Size::new(200.0, 100.0));
+
+        assert_eq!(
+            lengths.resolve_width(&min(parent_width(), px(150.0))),
+            Some(150.0)
+        );
+        assert_eq!(
+            lengths.resolve_width(&max(percent(10.0), px(50.0))),
+            Some(50.0)
+        );
+        assert_eq!(
+            lengths.resolve_width(&clamp(px(10.0), viewport_width() / 4.0, px(300.0))),
+            Some(300.0)
+        );
+        assert_eq!(
+            lengths.resolve_width(&clamp(px(10.0), px(2.0), px(300.0))),
+            Some(10.0)
+        );
+    }
+
+    #[test]
+    fn test_auto_and_indefinite() {
+        let lengths = lengths(Size::new(f32::INFINITY, 100.0));
+
+        assert_eq!(lengths.resolve_width(&(px(10.0) + Length::Auto)), None);
+        assert_eq!(lengths.resolve_width(&(percent(50.0) + 10.0)), None);
+        assert_eq!(lengths.resolve_width(&(px(10.0) / 0.0)), None);
+        assert_eq!(lengths.resolve_height(&(percent(50.0) + 10.0)), Some(60.0));
+    }
+
+    #[test]
+    fn test_units() {
+        let lengths = lengths(Size::new(200.0, 100.0));
+
+        assert_eq!(lengths.resolve_width(&vw(50.0)), Some(960.0));
+        assert_eq!(lengths.resolve_width(&vh(10.0)), Some(108.0));
+        assert_eq!(lengths.resolve_width(&vmin(100.0)), Some(1080.0));
+        assert_eq!(lengths.resolve_width(&vmax(100.0)), Some(1920.0));
+        assert_eq!(lengths.resolve_width(&em(1.5)), Some(24.0));
+        assert_eq!(lengths.resolve_width(&rem(2.0)), Some(24.0));
+        assert_eq!(lengths.resolve_width(&(vw(100.0) - em(2.0))), Some(1888.0));
+    }
+
+    #[test]
+    fn test_own_size() {
+        // An image keeping its aspect ratio inside a fixed-width column.
+        let style = Style::builder().height(width() * 0.5).build();
+        let node = MeasureNode::new(
+            &Viewport,
+            &style,
+            Constraints::new(Size::new(300.0, 0.0), Size::new(300.0, f32::INFINITY)),
+            Size::new(20.0, 20.0),
+        );
+
+        assert_eq!(node.outer.size, Size::new(300.0, 150.0));
+
+        let style = Style::builder()
+            .width(height() * 2.0)
+            .height(px(40.0))
+            .build();
+        let node = MeasureNode::new(&Viewport, &style, Constraints::UNBOUNDED, Size::default());
+
+        assert_eq!(node.outer.size, Size::new(80.0, 40.0));
+    }
+
+    #[test]
+    fn test_operation_enum() {
+        let lengths = lengths(Size::new(200.0, 100.0));
+        let length = Length::from(Operation::Sub(
+            Length::from(Operation::Mul(px(2.0), parent_width())),
+            px(50.0),
+        ));
+
+        assert_eq!(lengths.resolve_width(&length), Some(350.0));
+    }
+}
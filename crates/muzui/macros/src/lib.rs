@@ -36,6 +36,7 @@ enum NodeName {
     Grid,
     Image,
     Text,
+    Span,
     Custom(Ident),
 }
 
@@ -119,6 +120,8 @@ impl Parse for Node {
                     NodeName::Image
                 } else if name == "Text" {
                     NodeName::Text
+                } else if name == "Span" {
+                    NodeName::Span
                 } else {
                     NodeName::Custom(name)
                 }
@@ -147,6 +150,7 @@ impl From<Node> for TokenStream2 {
             NodeName::Grid => quote! { muzui::Node::grid },
             NodeName::Image => quote! { muzui::Node::image },
             NodeName::Text => quote! { muzui::Node::text },
+            NodeName::Span => quote! { muzui::Node::span },
             NodeName::Custom(name) => {
                 is_builtin = false;
 
@@ -222,6 +226,12 @@ impl From<Node> for TokenStream2 {
 ///             Text("Hello")
 ///             Text("World")
 ///         }
+///
+///         Text("Lv. ") {
+///             color: 0x808080,
+///
+///             Span("90") { font_weight: FontWeight::Bold }
+///         }
 ///     }
 /// };
 /// ```
@@ -0,0 +1,138 @@
+use std::{error::Error, fmt, time::Duration};
+
+/// A format of animations rendered by [`RenderContext::render_animation`](crate::RenderContext::render_animation),
+/// every format loops forever.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationFormat {
+    /// Colors are quantized to a palette of 256 per frame.
+    Gif,
+    Apng,
+    WebP,
+}
+
+/// A problem with rendering an animation.
+#[derive(Debug)]
+pub enum AnimationError {
+    /// There are no frames to encode.
+    Empty,
+    /// A frame has a different size than the first one, e.g. when the surface follows the content.
+    FrameSize,
+    /// The frames are too large for the format.
+    TooLarge,
+    /// The pixels of a frame couldn't be read.
+    Pixels,
+    Gif(gif::EncodingError),
+    Png(png::EncodingError),
+    WebP(webp_animation::Error),
+}
+
+impl fmt::Display for AnimationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("animation has no frames"),
+            Self::FrameSize => f.write_str("animation frames differ in size"),
+            Self::TooLarge => f.write_str("animation frames are too large for the format"),
+            Self::Pixels => f.write_str("failed to read the pixels of a frame"),
+            Self::Gif(error) => write!(f, "failed to encode GIF: {error}"),
+            Self::Png(error) => write!(f, "failed to encode APNG: {error}"),
+            Self::WebP(error) => write!(f, "failed to encode WebP: {error}"),
+        }
+    }
+}
+
+impl Error for AnimationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Gif(error) => Some(error),
+            Self::Png(error) => Some(error),
+            Self::WebP(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<gif::EncodingError> for AnimationError {
+    fn from(error: gif::EncodingError) -> Self {
+        Self::Gif(error)
+    }
+}
+
+impl From<png::EncodingError> for AnimationError {
+    fn from(error: png::EncodingError) -> Self {
+        Self::Png(error)
+    }
+}
+
+impl From<webp_animation::Error> for AnimationError {
+    fn from(error: webp_animation::Error) -> Self {
+        Self::WebP(error)
+    }
+}
+
+/// Encodes frames of unpremultiplied RGBA pixels, each shown for `delay`.
+pub(crate) fn encode(
+    format: AnimationFormat,
+    (width, height): (u32, u32),
+    frames: &mut [Vec<u8>],
+    delay: Duration,
+) -> Result<Vec<u8>, AnimationError> {
+    if frames.is_empty() {
+        return Err(AnimationError::Empty);
+    }
+
+    let millis = u16::try_from(delay.as_millis()).unwrap_or(u16::MAX);
+
+    let mut data = Vec::new();
+
+    match format {
+        AnimationFormat::Gif => {
+            let width = u16::try_from(width).map_err(|_| AnimationError::TooLarge)?;
+            let height = u16::try_from(height).map_err(|_| AnimationError::TooLarge)?;
+
+            let mut encoder = gif::Encoder::new(&mut data, width, height, &[])?;
+
+            encoder.set_repeat(gif::Repeat::Infinite)?;
+
+            for pixels in frames {
+                let mut frame = gif::Frame::from_rgba_speed(width, height, pixels, 10);
+
+                // GIF delays are in hundredths of a second.
+                frame.delay = millis / 10;
+
+                encoder.write_frame(&frame)?;
+            }
+        }
+        AnimationFormat::Apng => {
+            let count = u32::try_from(frames.len()).map_err(|_| AnimationError::TooLarge)?;
+
+            let mut encoder = png::Encoder::new(&mut data, width, height);
+
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.set_animated(count, 0)?;
+            encoder.set_frame_delay(millis, 1000)?;
+
+            let mut writer = encoder.write_header()?;
+
+            for pixels in frames.iter() {
+                writer.write_image_data(pixels)?;
+            }
+
+            writer.finish()?;
+        }
+        AnimationFormat::WebP => {
+            let mut encoder = webp_animation::Encoder::new((width, height))?;
+            let mut timestamp = 0;
+
+            for pixels in frames.iter() {
+                encoder.add_frame(pixels, timestamp)?;
+
+                timestamp += i32::from(millis);
+            }
+
+            data = encoder.finalize(timestamp)?.to_vec();
+        }
+    }
+
+    Ok(data)
+}
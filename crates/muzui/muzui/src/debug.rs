@@ -0,0 +1,109 @@
+use skia_safe::{Canvas, Color, Font, FontMgr, FontStyle, Paint, PaintStyle, RRect, Rect};
+
+use crate::{
+    layout::{MeasureNode, Measurer},
+    Element, Node,
+};
+
+const MARGIN: Color = Color::from_argb(0x50, 0xF5, 0x9E, 0x0B);
+const PADDING: Color = Color::from_argb(0x50, 0x22, 0xC5, 0x5E);
+const OUTER: Color = Color::from_argb(0xFF, 0x3B, 0x82, 0xF6);
+const INNER: Color = Color::from_argb(0xFF, 0xEF, 0x44, 0x44);
+const LABEL: Color = Color::from_argb(0xC0, 0x00, 0x00, 0x00);
+
+fn rect(rect: crate::layout::Rect) -> Rect {
+    Rect::from_xywh(
+        rect.origin.x,
+        rect.origin.y,
+        rect.size.width,
+        rect.size.height,
+    )
+}
+
+fn paint(color: Color, style: PaintStyle) -> Paint {
+    let mut paint = Paint::default();
+
+    paint.set_anti_alias(true);
+    paint.set_color(color);
+    paint.set_style(style);
+
+    paint
+}
+
+/// Draws the margin and padding areas, the outlines of the border and content boxes and a label
+/// with the kind, id and size of `node` and its descendants.
+///
+/// Spans share the rect of their paragraph, so nothing is drawn for them. Inline boxes inside
+/// a text or a span are drawn like any other node.
+pub(crate) fn draw_overlay(canvas: &Canvas, node: &Node, layout: &MeasureNode) {
+    let font = FontMgr::default()
+        .legacy_make_typeface(None, FontStyle::normal())
+        .map(|typeface| Font::new(typeface, 10.0));
+
+    draw_node(canvas, font.as_ref(), node, layout, false);
+}
+
+fn draw_node(canvas: &Canvas, font: Option<&Font>, node: &Node, layout: &MeasureNode, span: bool) {
+    let is_text = matches!(node.element(), Element::Text(_));
+
+    if !span {
+        let margin = node.get_style().margin.unwrap_or_default();
+
+        let outer = rect(layout.outer);
+        let inner = rect(layout.inner);
+        let margin = Rect::from_xywh(
+            outer.x() - margin.left,
+            outer.y() - margin.top,
+            outer.width() + margin.left + margin.right,
+            outer.height() + margin.top + margin.bottom,
+        );
+
+        canvas.draw_drrect(
+            RRect::new_rect(margin),
+            RRect::new_rect(outer),
+            &paint(MARGIN, PaintStyle::Fill),
+        );
+        canvas.draw_drrect(
+            RRect::new_rect(outer),
+            RRect::new_rect(inner),
+            &paint(PADDING, PaintStyle::Fill),
+        );
+        canvas.draw_rect(outer, &paint(OUTER, PaintStyle::Stroke));
+        canvas.draw_rect(inner, &paint(INNER, PaintStyle::Stroke));
+
+        if let Some(font) = font {
+            draw_label(canvas, font, node, outer);
+        }
+    }
+
+    for (child, layout) in node.element().children().iter().zip(&layout.children) {
+        let span = is_text && matches!(child.element(), Element::Text(_));
+
+        draw_node(canvas, font, child, layout, span);
+    }
+}
+
+fn draw_label(canvas: &Canvas, font: &Font, node: &Node, outer: Rect) {
+    let label = format!(
+        "{}{} {}×{}",
+        node.kind(),
+        node.id().map(|id| format!(" #{id}")).unwrap_or_default(),
+        outer.width(),
+        outer.height(),
+    );
+
+    let (width, _) = font.measure_str(&label, None);
+    let (_, metrics) = font.metrics();
+    let height = metrics.descent - metrics.ascent;
+
+    canvas.draw_rect(
+        Rect::from_xywh(outer.x(), outer.y(), width + 4.0, height + 2.0),
+        &paint(LABEL, PaintStyle::Fill),
+    );
+    canvas.draw_str(
+        &label,
+        (outer.x() + 2.0, outer.y() + 1.0 - metrics.ascent),
+        font,
+        &paint(Color::WHITE, PaintStyle::Fill),
+    );
+}
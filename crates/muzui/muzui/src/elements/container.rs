@@ -1,6 +1,6 @@
 use crate::{
-    graphics::Context,
-    layout::{Layout, MeasureNode, Measurer, Point, Rect, Size},
+    graphics::LayoutContext,
+    layout::{Constraints, Layout, MeasureNode, Measurer, Point, Size},
     styling::Style,
     Node,
 };
@@ -39,69 +39,135 @@ impl ContainerElement {
     }
 }
 
-impl Layout<Context> for ContainerElement {
-    fn measure(&self, context: &Context, style: &Style, parent: &Rect) -> MeasureNode {
-        let mut node = MeasureNode::new(style, parent, parent.size);
+impl Layout<LayoutContext> for ContainerElement {
+    fn measure(
+        &self,
+        context: &LayoutContext,
+        style: &Style,
+        constraints: Constraints,
+    ) -> MeasureNode {
+        let content = MeasureNode::content_constraints(context, style, constraints);
 
+        let mut children = Vec::with_capacity(self.children.len());
         let mut size = Size::default();
+        let mut offset = 0.0;
+        let mut placed = false;
 
-        let count = self.children.len();
+        for child in &self.children {
+            let style = child.get_style();
+            let child = child.measure(context, content);
 
-        match self.orientation {
-            Orientation::Vertical => {
-                for (index, child) in self.children.iter().enumerate() {
-                    let style = child.get_style();
-
-                    if style.position.is_relative() && index > 0 && index <= count {
-                        size.height += self.spacing;
-                    }
-
-                    let child = child.measure(context, &node.offset(Point::new(0.0, size.height)));
-
-                    if style.position.is_relative() {
-                        size.height += child.outer.size.height;
-
-                        if child.outer.size.width > size.width {
-                            size.width = child.outer.size.width;
-                        }
-                    }
-
-                    node.children.push(child);
+            if style.position.unwrap_or_default().is_relative() {
+                if placed {
+                    offset += self.spacing;
                 }
-            }
-            Orientation::Horizontal => {
-                for (index, child) in self.children.iter().enumerate() {
-                    let style = child.get_style();
 
-                    if style.position.is_relative() && index > 0 && index <= count {
-                        size.width += self.spacing;
-                    }
+                placed = true;
 
-                    let child = child.measure(context, &node.offset(Point::new(size.width, 0.0)));
+                let child_size = child.margin_size(style);
 
-                    if style.position.is_relative() {
-                        size.width += child.outer.size.width;
+                match self.orientation {
+                    Orientation::Vertical => {
+                        children.push((child, style, Point::new(0.0, offset)));
 
-                        if child.outer.size.height > size.height {
-                            size.height = child.outer.size.height;
-                        }
+                        offset += child_size.height;
+                        size.width = size.width.max(child_size.width);
                     }
+                    Orientation::Horizontal => {
+                        children.push((child, style, Point::new(offset, 0.0)));
 
-                    node.children.push(child);
+                        offset += child_size.width;
+                        size.height = size.height.max(child_size.height);
+                    }
                 }
+            } else {
+                children.push((child, style, Point::default()));
             }
         }
 
-        // println!("children: {:#?}", node.children);
-
-        if style.width.is_none() {
-            node.set_width(style, size.width);
+        match self.orientation {
+            Orientation::Vertical => size.height = offset,
+            Orientation::Horizontal => size.width = offset,
         }
 
-        if style.height.is_none() {
-            node.set_height(style, size.height);
+        let mut node = MeasureNode::new(context, style, constraints, size);
+
+        for (mut child, style, offset) in children {
+            child.set_position(
+                style,
+                if style.position.unwrap_or_default().is_absolute() {
+                    Point::new(style.x.unwrap_or_default(), style.y.unwrap_or_default())
+                } else {
+                    node.inner.origin + offset
+                },
+            );
+
+            node.children.push(child);
         }
 
         node
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        layout::Rect,
+        styling::{em, rem, Length, Position},
+        testing::{assert_rect, layout},
+        Node,
+    };
+
+    #[test]
+    fn test_absolute_children() {
+        let node = Node::column()
+            .padding(10.0)
+            .child(
+                Node::column()
+                    .id("parent")
+                    .margin(20.0)
+                    .padding(5.0)
+                    .size(Length::Px(100.0))
+                    .child(
+                        Node::column()
+                            .id("absolute")
+                            .position(Position::Absolute)
+                            .x(10.0)
+                            .y(20.0)
+                            .size(Length::Px(30.0))
+                            .build(),
+                    )
+                    .child(Node::column().id("relative").size(Length::Px(40.0)).build())
+                    .build(),
+            )
+            .build();
+
+        let tree = layout(&node, 400.0, 400.0);
+
+        assert_rect(&tree, "parent", Rect::from_xywh(30.0, 30.0, 100.0, 100.0));
+        // Offsets start at the border box of the parent, not at the canvas.
+        assert_rect(&tree, "absolute", Rect::from_xywh(40.0, 50.0, 30.0, 30.0));
+        // Absolute children take no space from their siblings.
+        assert_rect(&tree, "relative", Rect::from_xywh(35.0, 35.0, 40.0, 40.0));
+    }
+
+    #[test]
+    fn test_root_font_size() {
+        let node = Node::column()
+            .font_size(20.0)
+            .child(
+                Node::column()
+                    .id("child")
+                    .font_size(10.0)
+                    .width(rem(2.0))
+                    .height(em(2.0))
+                    .build(),
+            )
+            .build();
+
+        let tree = layout(&node, 400.0, 400.0);
+
+        // `rem` follows the root node, `em` the node itself.
+        assert_rect(&tree, "child", Rect::from_xywh(0.0, 0.0, 40.0, 20.0));
+    }
+}
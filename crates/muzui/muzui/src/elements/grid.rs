@@ -1,15 +1,75 @@
-use std::collections::HashMap;
+use std::{error::Error, fmt, iter, ops::Range};
 
-use muzui_layout::{Layout, MeasureNode, Measurer, Rect};
+use muzui_layout::{Constraints, Layout, MeasureNode, Measurer, Point, Size};
+pub use muzui_styling::GridFlow;
 use muzui_styling::{Length, Style};
 
-use crate::{graphics::Context, Node};
+use crate::{graphics::LayoutContext, Node};
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum GridLength {
+    /// Sized to fit the children of the track.
     Auto,
     Px(f32),
+    /// A share of the space left by the other tracks.
+    ///
+    /// Without a definite grid size, weighted tracks fit their children while keeping their proportions.
     Weight(f32),
+    /// Sized to fit the children, but no smaller than the first value and no larger than the second one.
+    ///
+    /// Grows into the space left by the other tracks up to its maximum.
+    MinMax(f32, f32),
+    /// Sized to fit the children, but no larger than the limit.
+    FitContent(f32),
+}
+
+impl GridLength {
+    #[must_use]
+    pub const fn minmax(min: f32, max: f32) -> Self {
+        Self::MinMax(min, max)
+    }
+
+    #[must_use]
+    pub const fn fit_content(limit: f32) -> Self {
+        Self::FitContent(limit)
+    }
+
+    /// The size of the track before its children are taken into account.
+    const fn base(&self) -> f32 {
+        match *self {
+            Self::Px(size) | Self::MinMax(size, _) => size,
+            Self::Auto | Self::Weight(_) | Self::FitContent(_) => 0.0,
+        }
+    }
+
+    /// How large children can make the track, `None` if they can't resize it at all.
+    fn limit(&self, definite: bool) -> Option<f32> {
+        match *self {
+            Self::Auto => Some(f32::INFINITY),
+            Self::Px(_) => None,
+            Self::Weight(_) => (!definite).then_some(f32::INFINITY),
+            Self::MinMax(min, max) => Some(max.max(min)),
+            Self::FitContent(limit) => Some(limit),
+        }
+    }
+
+    const fn weight(&self) -> Option<f32> {
+        if let Self::Weight(weight) = *self {
+            Some(weight)
+        } else {
+            None
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        let valid = |value: f32| value.is_finite() && value >= 0.0;
+
+        match *self {
+            Self::Auto => true,
+            Self::Px(value) | Self::Weight(value) | Self::FitContent(value) => valid(value),
+            Self::MinMax(min, max) => valid(min) && valid(max) && min <= max,
+        }
+    }
 }
 
 impl From<Length> for GridLength {
@@ -47,151 +107,860 @@ pub struct Column {
 //     Column { width }
 // }
 
-#[derive(Debug, Default, Clone)]
+/// A grid configuration that can't be laid out as specified.
+///
+/// Layout never fails on these: invalid tracks and gaps are clamped and children are moved
+/// into the nearest existing cell.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GridError {
+    /// A track has a negative or non-finite size, or a `minmax()` with its minimum above its maximum.
+    InvalidTrack(GridLength),
+    /// A gap is negative or non-finite.
+    InvalidGap(f32),
+    /// The rows of the area template have different numbers of cells.
+    UnevenAreas,
+    /// A child refers to an area missing from the template.
+    UnknownArea(String),
+    /// The cells of an area don't form a rectangle.
+    InvalidArea(String),
+    /// The child at the index spans no rows or no columns.
+    EmptySpan { child: usize },
+    /// The child at the index doesn't fit across the flow of the grid.
+    OutOfBounds { child: usize },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidTrack(track) => write!(f, "invalid grid track {track:?}"),
+            Self::InvalidGap(gap) => write!(f, "invalid grid gap {gap}"),
+            Self::UnevenAreas => write!(f, "grid area rows have different lengths"),
+            Self::UnknownArea(name) => write!(f, "unknown grid area {name}"),
+            Self::InvalidArea(name) => write!(f, "grid area {name} is not a rectangle"),
+            Self::EmptySpan { child } => write!(f, "grid child {child} spans no tracks"),
+            Self::OutOfBounds { child } => write!(f, "grid child {child} is outside of the grid"),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+#[derive(Debug, Clone)]
 pub struct GridElement {
     pub rows: Vec<Row>,
     pub columns: Vec<Column>,
-    pub spacing: f32,
+    /// Named areas, a name for every cell of every row, `.` for cells outside of any area.
+    pub areas: Vec<Vec<String>>,
+    pub flow: GridFlow,
+    /// The size of rows added past the defined ones.
+    pub auto_rows: GridLength,
+    /// The size of columns added past the defined ones.
+    pub auto_columns: GridLength,
+    pub row_gap: f32,
+    pub column_gap: f32,
     pub children: Vec<Node>,
 }
 
-impl Layout<Context> for GridElement {
-    #[allow(clippy::too_many_lines)]
-    fn measure(&self, context: &Context, style: &Style, parent: &Rect) -> MeasureNode {
-        assert!(
-            style.width.as_ref().is_some_and(|width| !width.is_auto()),
-            "the grid must have an explicitly specified width"
-        );
+impl Default for GridElement {
+    fn default() -> Self {
+        Self {
+            rows: Vec::new(),
+            columns: Vec::new(),
+            areas: Vec::new(),
+            flow: GridFlow::default(),
+            auto_rows: GridLength::Auto,
+            auto_columns: GridLength::Auto,
+            row_gap: 0.0,
+            column_gap: 0.0,
+            children: Vec::new(),
+        }
+    }
+}
 
-        assert!(
-            style.height.as_ref().is_some_and(|width| !width.is_auto()),
-            "the grid must have an explicitly specified height"
-        );
+/// A child's place along one axis and the size it needs there.
+#[derive(Debug, Clone)]
+struct Item {
+    tracks: Range<usize>,
+    size: f32,
+}
 
-        let mut root = MeasureNode::new(style, parent, parent.size);
+/// Where a child asks to be placed, along (`major`) and across (`minor`) the flow.
+#[derive(Debug, Clone, Copy)]
+struct Request {
+    major: Option<usize>,
+    major_span: usize,
+    minor: Option<usize>,
+    minor_span: usize,
+}
 
-        let mut rows = HashMap::new();
-        let mut columns = HashMap::new();
+/// The rows and columns covered by every child, and the number of rows and columns of the grid.
+#[derive(Debug)]
+struct Placement {
+    areas: Vec<(Range<usize>, Range<usize>)>,
+    rows: usize,
+    columns: usize,
+}
 
-        let mut available_width =
-            ((self.columns.len().max(1) - 1) as f32).mul_add(-self.spacing, root.inner.width());
-        let mut available_height =
-            ((self.rows.len().max(1) - 1) as f32).mul_add(-self.spacing, root.inner.height());
+/// Cells taken by the children placed so far, as lines along the flow.
+struct Occupancy {
+    lines: Vec<Vec<bool>>,
+    width: usize,
+}
 
-        let mut rows_weight = 0.0;
-        let mut columns_weight = 0.0;
+impl Occupancy {
+    fn fits(&self, major: usize, minor: usize, request: &Request) -> bool {
+        (major..major + request.major_span).all(|major| {
+            self.lines
+                .get(major)
+                .is_none_or(|line| !line[minor..minor + request.minor_span].contains(&true))
+        })
+    }
 
-        for (i, row) in self.rows.iter().enumerate() {
-            match row.height {
-                GridLength::Auto => {
-                    let size = self
-                        .children
-                        .iter()
-                        .filter(|node| {
-                            let style = node.get_style();
+    /// The first free position for `request` at or after `cursor`, in flow order.
+    fn find(&self, cursor: (usize, usize), request: &Request) -> (usize, usize) {
+        // Lines past the occupied ones are empty, so the search always ends there.
+        let end = self.lines.len().max(cursor.0) + 1;
+
+        request.minor.map_or_else(
+            || {
+                (cursor.0..=end)
+                    .find_map(|major| {
+                        let first = if major == cursor.0 { cursor.1 } else { 0 };
+
+                        (first..=self.width - request.minor_span)
+                            .find(|&minor| self.fits(major, minor, request))
+                            .map(|minor| (major, minor))
+                    })
+                    .unwrap_or((end, 0))
+            },
+            |minor| {
+                let start = if minor < cursor.1 {
+                    cursor.0 + 1
+                } else {
+                    cursor.0
+                };
+
+                let major = (start..=end)
+                    .find(|&major| self.fits(major, minor, request))
+                    .unwrap_or(end);
+
+                (major, minor)
+            },
+        )
+    }
 
-                            style.row == i && style.row_span == 1
-                        })
-                        .fold(0.0f32, |init, node| {
-                            init.max(node.measure(context, &root.inner).outer.size.height)
-                        });
+    fn occupy(&mut self, major: usize, minor: usize, request: &Request) {
+        let end = major + request.major_span;
 
-                    rows.insert(i, size);
+        if self.lines.len() < end {
+            self.lines.resize(end, vec![false; self.width]);
+        }
 
-                    available_height -= size;
-                }
-                GridLength::Px(size) => available_height -= size,
-                GridLength::Weight(weight) => rows_weight += weight,
+        for line in &mut self.lines[major..end] {
+            line[minor..minor + request.minor_span].fill(true);
+        }
+    }
+}
+
+impl GridElement {
+    /// Checks the tracks, gaps, areas and the placement of every child.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found.
+    pub fn validate(&self) -> Result<(), GridError> {
+        let tracks = self.rows.iter().map(|row| row.height);
+        let tracks = tracks
+            .chain(self.columns.iter().map(|column| column.width))
+            .chain([self.auto_rows, self.auto_columns]);
+
+        for track in tracks {
+            if !track.is_valid() {
+                return Err(GridError::InvalidTrack(track));
+            }
+        }
+
+        for gap in [self.row_gap, self.column_gap] {
+            if !gap.is_finite() || gap < 0.0 {
+                return Err(GridError::InvalidGap(gap));
             }
         }
 
-        for (i, column) in self.columns.iter().enumerate() {
-            match column.width {
-                GridLength::Auto => {
-                    let size = self
-                        .children
-                        .iter()
-                        .filter(|node| {
-                            let style = node.get_style();
+        if self
+            .areas
+            .windows(2)
+            .any(|rows| rows[0].len() != rows[1].len())
+        {
+            return Err(GridError::UnevenAreas);
+        }
 
-                            style.column == i && style.column_span == 1
-                        })
-                        .fold(0.0f32, |init, node| {
-                            init.max(node.measure(context, &root.inner).outer.size.width)
-                        });
+        let (rows, columns) = self.explicit_size();
+        let width = if self.flow.is_column() { rows } else { columns }.max(1);
 
-                    columns.insert(i, size);
+        for (index, child) in self.children.iter().enumerate() {
+            let style = child.get_style();
 
-                    available_height -= size;
-                }
-                GridLength::Px(size) => available_width -= size,
-                GridLength::Weight(weight) => columns_weight += weight,
+            if style.row_span == Some(0) || style.column_span == Some(0) {
+                return Err(GridError::EmptySpan { child: index });
+            }
+
+            if let Some(name) = &style.area {
+                self.area(name)?;
+
+                continue;
+            }
+
+            let (start, span) = if self.flow.is_column() {
+                (style.row, style.row_span.unwrap_or(1))
+            } else {
+                (style.column, style.column_span.unwrap_or(1))
+            };
+
+            if start.unwrap_or(0) + span > width {
+                return Err(GridError::OutOfBounds { child: index });
             }
         }
 
-        let base_height = available_height / rows_weight;
-        let base_width = available_width / columns_weight;
+        Ok(())
+    }
+
+    /// The number of rows and columns defined by the tracks and the area template.
+    fn explicit_size(&self) -> (usize, usize) {
+        let columns = self.areas.iter().map(Vec::len).max().unwrap_or(0);
 
-        let rows = self
-            .rows
+        (
+            self.rows.len().max(self.areas.len()),
+            self.columns.len().max(columns),
+        )
+    }
+
+    /// The rows and columns covered by the area called `name`.
+    fn area(&self, name: &str) -> Result<(Range<usize>, Range<usize>), GridError> {
+        let cells = self
+            .areas
             .iter()
             .enumerate()
-            .map(|(i, row)| {
+            .flat_map(|(row, names)| {
+                names
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, cell)| *cell == name)
+                    .map(move |(column, _)| (row, column))
+            })
+            .collect::<Vec<_>>();
+
+        let rows = cells.iter().map(|(row, _)| *row);
+        let columns = cells.iter().map(|(_, column)| *column);
+
+        match (
+            rows.clone().min(),
+            rows.max(),
+            columns.clone().min(),
+            columns.max(),
+        ) {
+            (Some(top), Some(bottom), Some(left), Some(right)) if name != "." => {
+                let area = (top..bottom + 1, left..right + 1);
+
+                if area.0.len() * area.1.len() == cells.len() {
+                    Ok(area)
+                } else {
+                    Err(GridError::InvalidArea(name.to_string()))
+                }
+            }
+            _ => Err(GridError::UnknownArea(name.to_string())),
+        }
+    }
+
+    /// Where the child with `style` asks to be placed, clamped to the `width` lines across the flow.
+    fn request(&self, style: &Style, column_flow: bool, width: usize) -> Request {
+        let (row, column) = style
+            .area
+            .as_deref()
+            .and_then(|name| self.area(name).ok())
+            .map_or(
                 (
-                    i,
-                    match row.height {
-                        GridLength::Auto => rows[&i],
-                        GridLength::Px(size) => size,
-                        GridLength::Weight(weight) => base_height * weight,
-                    },
-                )
+                    (style.row, style.row_span.unwrap_or(1)),
+                    (style.column, style.column_span.unwrap_or(1)),
+                ),
+                |(rows, columns)| {
+                    (
+                        (Some(rows.start), rows.len()),
+                        (Some(columns.start), columns.len()),
+                    )
+                },
+            );
+
+        let ((major, major_span), (minor, minor_span)) = if column_flow {
+            (column, row)
+        } else {
+            (row, column)
+        };
+
+        let minor_span = minor_span.clamp(1, width);
+
+        Request {
+            major,
+            major_span: major_span.max(1),
+            minor: minor.map(|minor| minor.min(width - minor_span)),
+            minor_span,
+        }
+    }
+
+    /// Places every child, adding rows or columns along the flow as needed.
+    fn place(&self) -> Placement {
+        let column_flow = self.flow.is_column();
+        let (rows, columns) = self.explicit_size();
+        let (length, width) = if column_flow {
+            (columns, rows.max(1))
+        } else {
+            (rows, columns.max(1))
+        };
+
+        let requests = self
+            .children
+            .iter()
+            .map(|child| self.request(child.get_style(), column_flow, width))
+            .collect::<Vec<_>>();
+
+        let mut occupancy = Occupancy {
+            lines: Vec::new(),
+            width,
+        };
+        let mut placed = vec![(0, 0); requests.len()];
+
+        // Children with a fixed cell go first, then the ones locked to a line along the flow.
+        for (index, request) in requests.iter().enumerate() {
+            if let (Some(major), Some(minor)) = (request.major, request.minor) {
+                occupancy.occupy(major, minor, request);
+                placed[index] = (major, minor);
+            }
+        }
+
+        for (index, request) in requests.iter().enumerate() {
+            if let (Some(major), None) = (request.major, request.minor) {
+                let minor = (0..=width - request.minor_span)
+                    .find(|&minor| occupancy.fits(major, minor, request))
+                    .unwrap_or(0);
+
+                occupancy.occupy(major, minor, request);
+                placed[index] = (major, minor);
+            }
+        }
+
+        // The rest follow each other in order, dense packing starts every search from the beginning.
+        let mut cursor = (0, 0);
+
+        for (index, request) in requests.iter().enumerate() {
+            if request.major.is_some() {
+                continue;
+            }
+
+            if self.flow.is_dense() {
+                cursor = (0, 0);
+            }
+
+            let position = occupancy.find(cursor, request);
+
+            occupancy.occupy(position.0, position.1, request);
+            placed[index] = position;
+            cursor = (position.0, position.1 + request.minor_span);
+        }
+
+        let length = placed
+            .iter()
+            .zip(&requests)
+            .map(|((major, _), request)| major + request.major_span)
+            .fold(length, usize::max);
+
+        let areas = placed
+            .into_iter()
+            .zip(requests)
+            .map(|((major, minor), request)| {
+                let major = major..major + request.major_span;
+                let minor = minor..minor + request.minor_span;
+
+                if column_flow {
+                    (minor, major)
+                } else {
+                    (major, minor)
+                }
             })
-            .collect::<HashMap<_, _>>();
+            .collect();
+
+        let (rows, columns) = if column_flow {
+            (width, length)
+        } else {
+            (length, width)
+        };
+
+        Placement {
+            areas,
+            rows,
+            columns,
+        }
+    }
+
+    /// The defined tracks followed by as many `auto` ones as needed to reach `count`.
+    fn tracks(
+        defined: impl Iterator<Item = GridLength>,
+        auto: GridLength,
+        count: usize,
+    ) -> Vec<GridLength> {
+        defined.chain(iter::repeat(auto)).take(count).collect()
+    }
+}
+
+fn sanitize_gap(gap: f32) -> f32 {
+    if gap.is_finite() {
+        gap.max(0.0)
+    } else {
+        0.0
+    }
+}
+
+fn gaps(count: usize, gap: f32) -> f32 {
+    count.saturating_sub(1) as f32 * gap
+}
 
-        let columns = self
-            .columns
+/// Distributes `extra` as evenly as possible between `tracks`, never growing a track past its limit.
+fn grow(sizes: &mut [f32], tracks: impl Iterator<Item = (usize, f32)>, mut extra: f32) {
+    let mut tracks = tracks
+        .map(|(index, limit)| (index, (limit - sizes[index]).max(0.0)))
+        .collect::<Vec<_>>();
+
+    // Tracks with the least room go first, so whatever they can't take is shared by the rest.
+    tracks.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+    for (taken, (index, room)) in tracks.iter().enumerate() {
+        if extra <= 0.0 {
+            break;
+        }
+
+        let grown = (extra / (tracks.len() - taken) as f32).min(*room);
+
+        sizes[*index] += grown;
+        extra -= grown;
+    }
+}
+
+/// Sizes the tracks of one axis.
+///
+/// `available` is the space of the grid's content box along the axis, infinite if it isn't definite.
+fn size_tracks(tracks: &[GridLength], items: &[Item], available: f32, gap: f32) -> Vec<f32> {
+    let definite = available.is_finite();
+    let total_gaps = gaps(tracks.len(), gap);
+
+    let mut sizes = tracks
+        .iter()
+        .map(|track| track.base().max(0.0))
+        .collect::<Vec<_>>();
+
+    let mut items = items.iter().collect::<Vec<_>>();
+
+    // Children spanning fewer tracks go first, so spanning children only add what's still missing.
+    items.sort_by_key(|item| item.tracks.len());
+
+    for item in items {
+        let range = item.tracks.clone();
+
+        // Weighted tracks take whatever space is left anyway.
+        if definite
+            && tracks[range.clone()]
+                .iter()
+                .any(|track| track.weight().is_some())
+        {
+            continue;
+        }
+
+        let current = sizes[range.clone()].iter().sum::<f32>() + gaps(range.len(), gap);
+        let intrinsic = range
+            .clone()
+            .filter_map(|index| tracks[index].limit(definite).map(|limit| (index, limit)));
+
+        grow(&mut sizes, intrinsic, item.size - current);
+    }
+
+    if definite {
+        let free = available - total_gaps - sizes.iter().sum::<f32>();
+        let growable = tracks.iter().enumerate().filter_map(|(index, track)| {
+            matches!(track, GridLength::MinMax(..))
+                .then(|| track.limit(definite).map(|limit| (index, limit)))
+                .flatten()
+        });
+
+        grow(&mut sizes, growable, free);
+    }
+
+    let weight = tracks
+        .iter()
+        .filter_map(GridLength::weight)
+        .map(|weight| weight.max(0.0))
+        .sum::<f32>();
+
+    if weight > 0.0 {
+        let fraction = if definite {
+            (available - total_gaps - sizes.iter().sum::<f32>()).max(0.0) / weight
+        } else {
+            tracks
+                .iter()
+                .zip(&sizes)
+                .filter_map(|(track, size)| {
+                    track
+                        .weight()
+                        .filter(|weight| *weight > 0.0)
+                        .map(|weight| size / weight)
+                })
+                .fold(0.0, f32::max)
+        };
+
+        for (track, size) in tracks.iter().zip(&mut sizes) {
+            if let Some(weight) = track.weight() {
+                *size = fraction * weight.max(0.0);
+            }
+        }
+    }
+
+    sizes
+}
+
+/// Offsets of every track from the start of the first one.
+fn offsets(sizes: &[f32], gap: f32) -> Vec<f32> {
+    sizes
+        .iter()
+        .scan(0.0, |offset, size| {
+            let start = *offset;
+
+            *offset += size + gap;
+
+            Some(start)
+        })
+        .collect()
+}
+
+fn span_size(sizes: &[f32], tracks: Range<usize>, gap: f32) -> f32 {
+    sizes[tracks.clone()].iter().sum::<f32>() + gaps(tracks.len(), gap)
+}
+
+impl Layout<LayoutContext> for GridElement {
+    fn measure(
+        &self,
+        context: &LayoutContext,
+        style: &Style,
+        constraints: Constraints,
+    ) -> MeasureNode {
+        let available = MeasureNode::content_constraints(context, style, constraints).max;
+
+        let row_gap = sanitize_gap(self.row_gap);
+        let column_gap = sanitize_gap(self.column_gap);
+
+        let Placement {
+            areas,
+            rows,
+            columns,
+        } = self.place();
+
+        let rows = Self::tracks(self.rows.iter().map(|row| row.height), self.auto_rows, rows);
+        let columns = Self::tracks(
+            self.columns.iter().map(|column| column.width),
+            self.auto_columns,
+            columns,
+        );
+
+        let items = self
+            .children
             .iter()
-            .enumerate()
-            .map(|(i, column)| {
-                (
-                    i,
-                    match column.width {
-                        GridLength::Auto => columns[&i],
-                        GridLength::Px(size) => size,
-                        GridLength::Weight(weight) => base_width * weight,
-                    },
-                )
+            .zip(&areas)
+            .map(|(child, (_, tracks))| Item {
+                tracks: tracks.clone(),
+                size: child
+                    .measure(
+                        context,
+                        Constraints::loose(Size::new(available.width, f32::INFINITY)),
+                    )
+                    .margin_size(child.get_style())
+                    .width,
+            })
+            .collect::<Vec<_>>();
+
+        let widths = size_tracks(&columns, &items, available.width, column_gap);
+
+        let items = self
+            .children
+            .iter()
+            .zip(&areas)
+            .map(|(child, (tracks, columns))| Item {
+                tracks: tracks.clone(),
+                size: child
+                    .measure(
+                        context,
+                        Constraints::loose(Size::new(
+                            span_size(&widths, columns.clone(), column_gap),
+                            f32::INFINITY,
+                        )),
+                    )
+                    .margin_size(child.get_style())
+                    .height,
             })
-            .collect::<HashMap<_, _>>();
-
-        for child in &self.children {
-            let Style {
-                row,
-                row_span,
-                column,
-                column_span,
-                ..
-            } = child.get_style();
-
-            let x = (0..*column).fold(
-                (*column as f32).mul_add(self.spacing, root.inner.origin.x),
-                |init, column| init + columns[&column],
+            .collect::<Vec<_>>();
+
+        let heights = size_tracks(&rows, &items, available.height, row_gap);
+
+        let size = Size::new(
+            span_size(&widths, 0..widths.len(), column_gap),
+            span_size(&heights, 0..heights.len(), row_gap),
+        );
+
+        let mut root = MeasureNode::new(context, style, constraints, size);
+
+        let xs = offsets(&widths, column_gap);
+        let ys = offsets(&heights, row_gap);
+
+        for (child, (rows, columns)) in self.children.iter().zip(areas) {
+            let position = Point::new(
+                root.inner.origin.x + xs[columns.start],
+                root.inner.origin.y + ys[rows.start],
             );
-            let y = (0..*row).fold(
-                (*row as f32).mul_add(self.spacing, root.inner.origin.y),
-                |init, row| init + rows[&row],
+            let cell = Size::new(
+                span_size(&widths, columns, column_gap),
+                span_size(&heights, rows, row_gap),
             );
-            let width =
-                (*column..*column + *column_span).fold(0.0, |init, column| init + columns[&column]);
-            let height = (*row..*row + *row_span).fold(0.0, |init, row| init + rows[&row]);
 
-            println!("{width}x{height} at {x}x{y} [{row}->{row_span} x {column}->{column_span}]");
+            let mut node = child.measure(context, Constraints::loose(cell));
 
-            let child = child.measure(context, &Rect::from_xywh(x, y, width, height));
+            node.set_position(child.get_style(), position);
 
-            root.children.push(child);
+            root.children.push(node);
         }
 
         root
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        size_tracks, Column, GridElement, GridError, GridFlow, GridLength, Item, Placement, Row,
+    };
+    use crate::{lang::style::Theme, Element, Node};
+
+    fn grid(columns: usize, children: Vec<Node>) -> GridElement {
+        GridElement {
+            columns: vec![Column::default(); columns],
+            children,
+            ..GridElement::default()
+        }
+    }
+
+    fn item(tracks: std::ops::Range<usize>, size: f32) -> Item {
+        Item { tracks, size }
+    }
+
+    #[test]
+    fn test_definite_tracks() {
+        let tracks = [
+            GridLength::Px(100.0),
+            GridLength::Auto,
+            GridLength::Weight(1.0),
+            GridLength::Weight(3.0),
+        ];
+
+        let sizes = size_tracks(&tracks, &[item(1..2, 50.0)], 630.0, 10.0);
+
+        assert_eq!(sizes, [100.0, 50.0, 112.5, 337.5]);
+    }
+
+    #[test]
+    fn test_indefinite_tracks() {
+        let tracks = [
+            GridLength::Auto,
+            GridLength::Weight(1.0),
+            GridLength::Weight(2.0),
+        ];
+
+        let sizes = size_tracks(
+            &tracks,
+            &[item(0..1, 40.0), item(1..2, 30.0), item(2..3, 20.0)],
+            f32::INFINITY,
+            0.0,
+        );
+
+        assert_eq!(sizes, [40.0, 30.0, 60.0]);
+    }
+
+    #[test]
+    fn test_spanning_children() {
+        let tracks = [GridLength::Auto, GridLength::Auto, GridLength::Px(10.0)];
+
+        let sizes = size_tracks(
+            &tracks,
+            &[item(0..1, 20.0), item(0..3, 100.0)],
+            f32::INFINITY,
+            5.0,
+        );
+
+        assert_eq!(sizes, [50.0, 30.0, 10.0]);
+    }
+
+    #[test]
+    fn test_minmax_and_fit_content() {
+        let tracks = [
+            GridLength::minmax(50.0, 100.0),
+            GridLength::fit_content(30.0),
+            GridLength::Auto,
+        ];
+        let items = [item(0..1, 10.0), item(1..2, 80.0), item(2..3, 20.0)];
+
+        assert_eq!(
+            size_tracks(&tracks, &items, f32::INFINITY, 0.0),
+            [50.0, 30.0, 20.0]
+        );
+        assert_eq!(size_tracks(&tracks, &items, 120.0, 0.0), [70.0, 30.0, 20.0]);
+        assert_eq!(
+            size_tracks(&tracks, &items, 500.0, 0.0),
+            [100.0, 30.0, 20.0]
+        );
+    }
+
+    #[test]
+    fn test_auto_flow() {
+        let mut grid = grid(
+            3,
+            vec![
+                Node::column().build(),
+                Node::column().column_span(3).build(),
+                Node::column().build(),
+                Node::column().row(0).build(),
+            ],
+        );
+
+        let Placement {
+            areas,
+            rows,
+            columns,
+        } = grid.place();
+
+        assert_eq!((rows, columns), (3, 3));
+        assert_eq!(
+            areas,
+            [(0..1, 1..2), (1..2, 0..3), (2..3, 0..1), (0..1, 0..1)]
+        );
+
+        grid.flow = GridFlow::RowDense;
+
+        let Placement { areas, rows, .. } = grid.place();
+
+        assert_eq!(rows, 2);
+        assert_eq!(
+            areas,
+            [(0..1, 1..2), (1..2, 0..3), (0..1, 2..3), (0..1, 0..1)]
+        );
+    }
+
+    #[test]
+    fn test_parsed_grid() {
+        let data = serde_json::json!({ "characters": { "list": ["Amber", "Kaeya", "Lisa"] } });
+
+        let node = Node::parse(
+            r#"Grid {
+                flow: column dense,
+                areas: "a a" "b c",
+                column-gap: 8,
+
+                for c in characters.list { Text(c) }
+            }"#,
+            &Theme::new(),
+            &data,
+        )
+        .expect("failed to parse layout");
+
+        let Element::Grid(grid) = node.element() else {
+            panic!("expected a grid, found {}", node.kind());
+        };
+
+        assert_eq!(grid.flow, GridFlow::ColumnDense);
+        assert_eq!((grid.row_gap, grid.column_gap), (0.0, 8.0));
+        assert_eq!(grid.areas.len(), 2);
+        assert_eq!(grid.children.len(), 3);
+        assert_eq!(grid.children[2].kind(), "Text");
+    }
+
+    #[test]
+    fn test_column_flow() {
+        let grid = GridElement {
+            rows: vec![Row::default(); 2],
+            flow: GridFlow::Column,
+            children: vec![
+                Node::column().build(),
+                Node::column().build(),
+                Node::column().build(),
+            ],
+            ..GridElement::default()
+        };
+
+        let Placement {
+            areas,
+            rows,
+            columns,
+        } = grid.place();
+
+        assert_eq!((rows, columns), (2, 2));
+        assert_eq!(areas, [(0..1, 0..1), (1..2, 0..1), (0..1, 1..2)]);
+    }
+
+    #[test]
+    fn test_areas() {
+        let mut grid = grid(
+            0,
+            vec![
+                Node::column().area("main").build(),
+                Node::column().area("header").build(),
+                Node::column().build(),
+            ],
+        );
+
+        grid.areas = ["header header", "side main"]
+            .iter()
+            .map(|row| row.split_whitespace().map(ToString::to_string).collect())
+            .collect();
+
+        let Placement {
+            areas,
+            rows,
+            columns,
+        } = grid.place();
+
+        assert_eq!((rows, columns), (2, 2));
+        assert_eq!(areas, [(1..2, 1..2), (0..1, 0..2), (1..2, 0..1)]);
+        assert_eq!(grid.validate(), Ok(()));
+
+        grid.children.push(Node::column().area("footer").build());
+
+        assert_eq!(
+            grid.validate(),
+            Err(GridError::UnknownArea("footer".to_string()))
+        );
+
+        grid.areas[1].reverse();
+        grid.areas[1][1] = "header".to_string();
+
+        assert_eq!(
+            grid.validate(),
+            Err(GridError::InvalidArea("header".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_invalid_grid() {
+        let mut grid = grid(2, vec![Node::column().column(1).column_span(2).build()]);
+
+        assert_eq!(grid.validate(), Err(GridError::OutOfBounds { child: 0 }));
+        assert_eq!(grid.place().areas, [(0..1, 0..2)]);
+
+        grid.columns[0].width = GridLength::minmax(10.0, 5.0);
+
+        assert_eq!(
+            grid.validate(),
+            Err(GridError::InvalidTrack(GridLength::MinMax(10.0, 5.0)))
+        );
+    }
+}
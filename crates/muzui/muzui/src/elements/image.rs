@@ -1,6 +1,6 @@
 use crate::{
-    graphics::Context,
-    layout::{Layout, MeasureNode, Rect, Size},
+    graphics::LayoutContext,
+    layout::{Constraints, Layout, MeasureNode, Size},
     styling::Style,
 };
 use skia_safe::{Data, Image};
@@ -27,11 +27,17 @@ impl ImageElement {
     }
 }
 
-impl Layout<Context> for ImageElement {
-    fn measure(&self, _: &Context, style: &Style, parent: &Rect) -> MeasureNode {
+impl Layout<LayoutContext> for ImageElement {
+    fn measure(
+        &self,
+        context: &LayoutContext,
+        style: &Style,
+        constraints: Constraints,
+    ) -> MeasureNode {
         MeasureNode::new(
+            context,
             style,
-            parent,
+            constraints,
             #[allow(clippy::cast_precision_loss)]
             Size::new(self.data.width() as f32, self.data.height() as f32),
         )
@@ -1,6 +1,6 @@
 use crate::{
-    graphics::Context,
-    layout::{Layout, MeasureNode, Measurer, Rect},
+    graphics::LayoutContext,
+    layout::{Constraints, Layout, MeasureNode, Measurer, Point, Size},
     styling::Style,
     Node,
 };
@@ -9,6 +9,12 @@ use crate::{
 pub struct Masonry {
     pub spacing: f32,
     pub item_width: f32,
+    /// The fewest columns used, items shrink below `item_width` to fit them into a bounded width.
+    /// Also the number of columns when the width is unbounded.
+    pub min_columns: usize,
+    pub max_columns: Option<usize>,
+    /// Places item N into column N % columns instead of the shortest one, keeping the reading order.
+    pub preserve_order: bool,
     pub children: Vec<Node>,
 }
 
@@ -18,75 +24,173 @@ impl Masonry {
         Self {
             spacing: 0.0,
             item_width,
+            min_columns: 1,
+            max_columns: None,
+            preserve_order: false,
             children: Vec::new(),
         }
     }
 
-    fn columns(&self, available_width: f32) -> f32 {
-        (available_width / (self.item_width + self.spacing)).floor()
-    }
+    fn columns(&self, available_width: Option<f32>) -> usize {
+        let min = self.min_columns.max(1);
+        let max = self.max_columns.map_or(usize::MAX, |max| max.max(min));
 
-    fn item_width(&self, available_width: f32) -> f32 {
-        let columns = self.columns(available_width);
+        let step = self.item_width + self.spacing;
+
+        // Without a positive step any number of columns fits, so fall back to the fewest.
+        available_width.filter(|_| step > 0.0).map_or(min, |width| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let fits = ((width + self.spacing) / step).floor() as usize;
+
+            fits.clamp(min, max)
+        })
+    }
 
-        self.spacing.mul_add(-(columns - 1.0), available_width) / columns
+    fn item_width(&self, available_width: Option<f32>, columns: usize) -> f32 {
+        available_width.map_or(self.item_width, |width| {
+            (self.spacing.mul_add(-(columns as f32 - 1.0), width) / columns as f32).max(0.0)
+        })
     }
 }
 
-impl Layout<Context> for Masonry {
-    fn measure(&self, context: &Context, style: &Style, parent: &Rect) -> MeasureNode {
-        let mut node = MeasureNode::from_parent(style, parent);
+/// Assigns every item of the given height a column and a vertical offset,
+/// returning the positions along with the height of the tallest column.
+fn place(
+    heights: &[f32],
+    columns: usize,
+    spacing: f32,
+    preserve_order: bool,
+) -> (Vec<(usize, f32)>, f32) {
+    // Columns past the number of items stay empty either way.
+    let mut bottoms = vec![0.0f32; columns.min(heights.len()).max(1)];
+
+    let positions = heights
+        .iter()
+        .enumerate()
+        .map(|(index, height)| {
+            let column = if preserve_order {
+                index % columns
+            } else {
+                // The leftmost one wins on ties.
+                bottoms
+                    .iter()
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                    .map_or(0, |(column, _)| column)
+            };
+
+            let position = (column, bottoms[column]);
+
+            bottoms[column] += height + spacing;
+
+            position
+        })
+        .collect();
+
+    let height = if heights.is_empty() {
+        0.0
+    } else {
+        bottoms.into_iter().fold(0.0f32, f32::max) - spacing
+    };
+
+    (positions, height)
+}
+
+impl Layout<LayoutContext> for Masonry {
+    fn measure(
+        &self,
+        context: &LayoutContext,
+        style: &Style,
+        constraints: Constraints,
+    ) -> MeasureNode {
+        let bounds = MeasureNode::content_constraints(context, style, constraints);
 
-        #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-        let columns = self.columns(node.inner.size.width) as usize;
-        let item_width = self.item_width(node.inner.size.width);
+        let available_width = bounds.has_bounded_width().then_some(bounds.max.width);
 
-        for child in self
+        let columns = self.columns(available_width);
+        let item_width = self.item_width(available_width, columns);
+
+        let item = Constraints::new(
+            Size::new(item_width, 0.0),
+            Size::new(item_width, f32::INFINITY),
+        );
+
+        let children = self
             .children
-            .chunks(columns)
-            .fold(Vec::new(), |mut rows, row| {
-                let last = rows.last();
+            .iter()
+            .map(|child| (child.measure(context, item), child.get_style()))
+            .collect::<Vec<_>>();
+
+        let heights = children
+            .iter()
+            .map(|(child, style)| child.margin_size(style).height)
+            .collect::<Vec<_>>();
+
+        let (positions, height) = place(&heights, columns, self.spacing, self.preserve_order);
+
+        let mut node = MeasureNode::new(
+            context,
+            style,
+            constraints,
+            Size::new(
+                available_width.unwrap_or_else(|| {
+                    self.spacing
+                        .mul_add(columns as f32 - 1.0, item_width * columns as f32)
+                }),
+                height,
+            ),
+        );
+
+        for ((mut child, style), (column, y)) in children.into_iter().zip(positions) {
+            let x = (item_width + self.spacing) * column as f32;
+
+            child.set_position(style, node.inner.origin + Point::new(x, y));
 
-                let row = row
-                    .iter()
-                    .enumerate()
-                    .map(|(x, child)| {
-                        let y = if rows.is_empty() {
-                            node.inner.origin.y
-                        } else {
-                            0.0
-                        } + last
-                            .and_then(|last: &Vec<MeasureNode>| last.get(x))
-                            .map(|last| last.outer.origin.y + last.outer.size.height + self.spacing)
-                            .unwrap_or_default();
-                        #[allow(clippy::cast_precision_loss)]
-                        let x = (item_width + self.spacing).mul_add(x as f32, node.inner.origin.x);
-
-                        let style = child.get_style();
-                        let mut base = MeasureNode::from_parent(
-                            style,
-                            &Rect::from_xywh(x, y, item_width, 0.0),
-                        );
-
-                        let child = child.measure(context, &base.inner);
-
-                        base.set_height(style, child.outer.size.height);
-                        base.children = child.children;
-
-                        base
-                    })
-                    .collect::<Vec<_>>();
-
-                rows.push(row);
-
-                rows
-            })
-            .into_iter()
-            .flatten()
-        {
             node.children.push(child);
         }
 
         node
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{place, Masonry};
+
+    #[test]
+    fn test_shortest_column_first() {
+        let (positions, height) = place(&[100.0, 20.0, 30.0, 10.0], 2, 5.0, false);
+
+        assert_eq!(positions, [(0, 0.0), (1, 0.0), (1, 25.0), (1, 60.0)]);
+        assert_eq!(height, 100.0);
+
+        let (positions, height) = place(&[100.0, 20.0, 30.0, 10.0], 2, 5.0, true);
+
+        assert_eq!(positions, [(0, 0.0), (1, 0.0), (0, 105.0), (1, 25.0)]);
+        assert_eq!(height, 135.0);
+
+        assert_eq!(place(&[], 3, 5.0, false), (Vec::new(), 0.0));
+        assert_eq!(
+            place(&[10.0, 20.0], usize::MAX, 5.0, false),
+            (vec![(0, 0.0), (1, 0.0)], 20.0)
+        );
+    }
+
+    #[test]
+    fn test_non_positive_step() {
+        let mut masonry = Masonry::new(0.0);
+
+        masonry.min_columns = 2;
+
+        assert_eq!(masonry.columns(Some(300.0)), 2);
+
+        masonry.item_width = 10.0;
+        masonry.spacing = -20.0;
+
+        assert_eq!(masonry.columns(Some(300.0)), 2);
+
+        masonry.spacing = 5.0;
+
+        assert_eq!(masonry.columns(Some(300.0)), 20);
+    }
+}
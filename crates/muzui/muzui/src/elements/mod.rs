@@ -1,3 +1,7 @@
+use crate::Node;
+
+use self::container::Orientation;
+
 pub use self::{
     container::ContainerElement, grid::*, image::ImageElement, masonry::Masonry, text::TextElement,
 };
@@ -17,3 +21,42 @@ pub enum Element {
     Grid(GridElement),
     Text(TextElement),
 }
+
+impl Element {
+    /// The element type name matched by stylesheet selectors.
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Container(element) => match element.orientation {
+                Orientation::Vertical => "Column",
+                Orientation::Horizontal => "Row",
+            },
+            Self::Masonry(_) => "Masonry",
+            Self::Image(_) => "Image",
+            Self::Grid(_) => "Grid",
+            Self::Text(element) if element.span => "Span",
+            Self::Text(_) => "Text",
+        }
+    }
+
+    #[must_use]
+    pub fn children(&self) -> &[Node] {
+        match self {
+            Self::Container(element) => &element.children,
+            Self::Masonry(element) => &element.children,
+            Self::Grid(element) => &element.children,
+            Self::Text(element) => &element.children,
+            Self::Image(_) => &[],
+        }
+    }
+
+    pub fn children_mut(&mut self) -> &mut [Node] {
+        match self {
+            Self::Container(element) => &mut element.children,
+            Self::Masonry(element) => &mut element.children,
+            Self::Grid(element) => &mut element.children,
+            Self::Text(element) => &mut element.children,
+            Self::Image(_) => &mut [],
+        }
+    }
+}
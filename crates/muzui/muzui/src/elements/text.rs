@@ -1,35 +1,250 @@
 use crate::{
-    graphics::Context,
-    layout::{Layout, MeasureNode, Rect, Size},
-    styling::Style,
+    graphics::{LayoutContext, Run},
+    layout::{Constraints, Layout, MeasureNode, Measurer, Point, Rect, Size},
+    styling::{Style, TextAlign},
+    Element, Node,
 };
+use skia_safe::Canvas;
 
+/// A paragraph of text.
+///
+/// Children flow inline after `data`: text children (spans) add their text with their own style,
+/// any other child is measured and placed into the line as an inline box, e.g. an icon.
 #[derive(Debug, Clone)]
 pub struct TextElement {
     pub data: String,
+    /// Whether this is a span, only changing the kind matched by stylesheets.
+    pub span: bool,
+    pub children: Vec<Node>,
 }
 
 impl TextElement {
     #[must_use]
     pub const fn new(data: String) -> Self {
-        Self { data }
+        Self {
+            data,
+            span: false,
+            children: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub const fn span(data: String) -> Self {
+        Self {
+            data,
+            span: true,
+            children: Vec::new(),
+        }
+    }
+
+    /// Describes the text of this element and its spans as runs, with a placeholder
+    /// of the size returned by `measure` for every inline box.
+    fn runs<F: FnMut(&Node) -> Size>(&self, style: &Style, runs: &mut Vec<Run>, measure: &mut F) {
+        runs.push(Run::Push(Box::new(style.clone())));
+        runs.push(Run::Text(
+            style.text_transform.unwrap_or_default().apply(&self.data),
+        ));
+
+        for child in &self.children {
+            if let Element::Text(text) = child.element() {
+                let mut span = child.get_style().clone();
+
+                span.inherit(style);
+                // Decorations aren't inherited, but they're drawn across the spans they contain.
+                span.text_decoration = span.text_decoration.or(style.text_decoration);
+
+                text.runs(&span, runs, measure);
+            } else {
+                runs.push(Run::Placeholder(measure(child)));
+            }
+        }
+
+        runs.push(Run::Pop);
+    }
+
+    /// Builds the measure nodes of the children, spans take the rect of the paragraph
+    /// while inline boxes are moved to their placeholders, relative to its origin.
+    fn arrange(
+        &self,
+        bounds: Rect,
+        boxes: &mut impl Iterator<Item = (MeasureNode, Point)>,
+    ) -> Vec<MeasureNode> {
+        self.children
+            .iter()
+            .filter_map(|child| {
+                if let Element::Text(text) = child.element() {
+                    Some(MeasureNode {
+                        outer: bounds,
+                        inner: bounds,
+                        children: text.arrange(Rect::new(Point::default(), bounds.size), boxes),
+                    })
+                } else {
+                    boxes.next().map(|(mut node, point)| {
+                        node.set_position(child.get_style(), bounds.origin + point);
+
+                        node
+                    })
+                }
+            })
+            .collect()
+    }
+
+    /// Pairs the inline boxes of this element and its spans with their measure nodes.
+    fn inline_boxes<'a>(
+        &'a self,
+        nodes: Vec<MeasureNode>,
+        boxes: &mut Vec<(&'a Node, MeasureNode)>,
+    ) {
+        for (child, node) in self.children.iter().zip(nodes) {
+            if let Element::Text(text) = child.element() {
+                text.inline_boxes(node.children, boxes);
+            } else {
+                boxes.push((child, node));
+            }
+        }
+    }
+
+    pub(crate) fn draw(
+        &self,
+        canvas: &Canvas,
+        context: &LayoutContext,
+        style: &Style,
+        node: MeasureNode,
+    ) {
+        let mut boxes = Vec::new();
+
+        self.inline_boxes(node.children, &mut boxes);
+
+        let mut sizes = boxes
+            .iter()
+            .map(|(child, node)| node.margin_size(child.get_style()));
+
+        let mut runs = Vec::new();
+
+        self.runs(style, &mut runs, &mut |_| sizes.next().unwrap_or_default());
+
+        let origin = (node.inner.origin.x, node.inner.origin.y);
+
+        if let Some(stroke) = context.paragraph_stroke(runs.clone(), node.inner.size.width) {
+            stroke.paint(canvas, origin);
+        }
+
+        context
+            .paragraph(runs, node.inner.size.width)
+            .paint(canvas, origin);
+
+        for (child, node) in boxes {
+            child.draw(canvas, context, node);
+        }
     }
 }
 
-impl Layout<Context> for TextElement {
-    fn measure(&self, context: &Context, style: &Style, parent: &Rect) -> MeasureNode {
-        let mut paragraph = context.create_paragraph(style, &self.data);
+impl Layout<LayoutContext> for TextElement {
+    fn measure(
+        &self,
+        context: &LayoutContext,
+        style: &Style,
+        constraints: Constraints,
+    ) -> MeasureNode {
+        let width = MeasureNode::content_constraints(context, style, constraints)
+            .max
+            .width;
 
-        paragraph.layout(if parent.size.width == 0.0 {
-            context.bounds.width()
-        } else {
-            parent.size.width
+        let inline = Constraints::loose(Size::new(width, f32::INFINITY));
+
+        let mut runs = Vec::new();
+        let mut boxes = Vec::new();
+
+        self.runs(style, &mut runs, &mut |child| {
+            let node = child.measure(context, inline);
+            let size = node.margin_size(child.get_style());
+
+            boxes.push(node);
+
+            size
         });
 
-        MeasureNode::new(
+        let paragraph = context.paragraph(runs.clone(), width);
+
+        let mut node = MeasureNode::new(
+            context,
             style,
-            parent,
+            constraints,
             Size::new(paragraph.longest_line() + 1.0, paragraph.height()),
+        );
+
+        // Left aligned lines stay in place when the paragraph is narrowed down to its content,
+        // so drawing it at the width of the node can skip another layout. Other alignments move
+        // the lines, so the inline boxes are placed from the layout that gets drawn.
+        let paragraph = if matches!(
+            style.text_align.unwrap_or_default(),
+            TextAlign::Left | TextAlign::Start
+        ) {
+            context.reuse_paragraph(runs, node.inner.size.width, paragraph.clone());
+
+            paragraph
+        } else {
+            context.paragraph(runs, node.inner.size.width)
+        };
+
+        let placeholders = paragraph.get_rects_for_placeholders();
+
+        let mut boxes = boxes.into_iter().enumerate().map(|(index, node)| {
+            let point = placeholders
+                .get(index)
+                .map_or_else(Point::default, |placeholder| {
+                    Point::new(placeholder.rect.left, placeholder.rect.top)
+                });
+
+            (node, point)
+        });
+
+        node.children = self.arrange(node.inner, &mut boxes);
+
+        node
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lang::style::Theme,
+        layout::Measurer,
+        styling::{Color, FontWeight},
+        Node,
+    };
+
+    #[test]
+    fn test_spans() {
+        let theme = Theme::from([("secondary".to_string(), Color::from_u32(0xFF44_5566))]);
+
+        let node = Node::parse(
+            r#"style {
+                Span { font-weight: bold }
+            }
+
+            Text {
+                Span("Lv. ") { color: theme.secondary }
+                Span("{level}")
+            }"#,
+            &theme,
+            &serde_json::json!({ "level": 90 }),
         )
+        .expect("failed to parse layout");
+
+        let spans = node.element().children();
+
+        assert_eq!(node.kind(), "Text");
+        assert_eq!(node.get_style().font_weight, None);
+        assert_eq!(
+            spans.iter().map(Node::kind).collect::<Vec<_>>(),
+            ["Span", "Span"]
+        );
+        assert_eq!(
+            spans[0].get_style().color,
+            Some(Color::from_u32(0xFF44_5566))
+        );
+        assert_eq!(spans[1].get_style().font_weight, Some(FontWeight::Bold));
+        assert_eq!(Node::span("a").build().kind(), "Span");
     }
 }
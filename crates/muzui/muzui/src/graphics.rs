@@ -1,95 +1,952 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    error::Error,
+    fmt, fs,
+    hash::{Hash, Hasher},
+    io,
+    path::Path,
+    rc::Rc,
+    time::Duration,
+};
+
 use skia_safe::{
-    font_style::{Slant, Weight, Width}, surfaces, textlayout::{FontCollection, Paragraph, ParagraphBuilder, ParagraphStyle, TextStyle}, EncodedImageFormat, FontMgr, FontStyle, Image, RCHandle, Surface
+    font_style::{Slant, Weight, Width},
+    pdf, surfaces, svg,
+    textlayout::{
+        self, FontCollection, Paragraph, ParagraphBuilder, ParagraphStyle, PlaceholderAlignment,
+        PlaceholderStyle, TextBaseline, TextShadow, TextStyle, TypefaceFontProvider,
+    },
+    AlphaType, ColorType, EncodedImageFormat, FontMgr, FontStyle, ImageInfo, Paint, PaintJoin,
+    PaintStyle, Rect as SkRect, Surface, Typeface,
 };
 
 use crate::{
-    layout::{Measurer, Rect},
-    styling::{Color, FontSlant, FontWeight, Style},
-    Node,
+    animation::{self, AnimationError, AnimationFormat},
+    debug,
+    lang::{
+        parser::ParseError,
+        style::{parse_stylesheet_source, Theme},
+    },
+    layout::{Constraints, Environment, MeasureNode, Measurer, Point, Rect, Size},
+    styling::{
+        Color, FontFamily, FontSize, FontSlant, FontWeight, FontWidth, Style, Stylesheet,
+        TextAlign, TextDecoration,
+    },
+    LayoutTree, Node,
 };
 
-pub struct Context {
+/// A problem with fonts.
+///
+/// Rendering never fails on a missing family, its text falls back to the next family
+/// of the list or to a system font.
+#[derive(Debug)]
+pub enum FontError {
+    /// The font file couldn't be read.
+    Io(io::Error),
+    /// The data isn't a font that can be loaded.
+    InvalidData,
+    /// Neither the registered nor the system fonts have the family.
+    Missing(String),
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to read font: {error}"),
+            Self::InvalidData => write!(f, "invalid font data"),
+            Self::Missing(family) => write!(f, "font family {family} is not available"),
+        }
+    }
+}
+
+impl Error for FontError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::InvalidData | Self::Missing(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for FontError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// A piece of a paragraph.
+#[derive(Debug, Clone)]
+pub enum Run {
+    /// Draws the following runs with `style`, until the matching [`Run::Pop`].
+    Push(Box<Style>),
+    Text(String),
+    /// A box reserved for an inline child, vertically centered on the line.
+    Placeholder(Size),
+    Pop,
+}
+
+/// The properties of a style that change how text is laid out or drawn.
+#[derive(PartialEq, Eq, Hash)]
+struct TextKey<'a> {
+    family: Option<&'a FontFamily>,
+    weight: Option<FontWeight>,
+    width: Option<FontWidth>,
+    slant: Option<FontSlant>,
+    size: Option<u32>,
+    color: Option<u32>,
+    line_height: Option<u32>,
+    letter_spacing: Option<u32>,
+    word_spacing: Option<u32>,
+    decoration: Option<TextDecoration>,
+    shadows: Option<Vec<[u32; 4]>>,
+    stroke: Option<(u32, u32)>,
+    align: Option<TextAlign>,
+    max_lines: Option<usize>,
+}
+
+impl<'a> TextKey<'a> {
+    fn new(style: &'a Style) -> Self {
+        Self {
+            family: style.font_family.as_ref(),
+            weight: style.font_weight,
+            width: style.font_width,
+            slant: style.font_slant,
+            size: style.font_size.map(|size| size.size.to_bits()),
+            color: style.color.as_ref().map(Color::as_u32),
+            line_height: style.line_height.map(f32::to_bits),
+            letter_spacing: style.letter_spacing.map(f32::to_bits),
+            word_spacing: style.word_spacing.map(f32::to_bits),
+            decoration: style.text_decoration,
+            shadows: style.text_shadow.as_ref().map(|shadows| {
+                shadows
+                    .iter()
+                    .map(|shadow| {
+                        [
+                            shadow.color.as_u32(),
+                            shadow.x.to_bits(),
+                            shadow.y.to_bits(),
+                            shadow.blur.to_bits(),
+                        ]
+                    })
+                    .collect()
+            }),
+            stroke: style
+                .text_stroke
+                .map(|stroke| (stroke.width.to_bits(), stroke.color.as_u32())),
+            align: style.text_align,
+            max_lines: style.max_lines,
+        }
+    }
+}
+
+impl PartialEq for Run {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Push(a), Self::Push(b)) => TextKey::new(a) == TextKey::new(b),
+            (Self::Text(a), Self::Text(b)) => a == b,
+            (Self::Placeholder(a), Self::Placeholder(b)) => {
+                a.width.to_bits() == b.width.to_bits() && a.height.to_bits() == b.height.to_bits()
+            }
+            (Self::Pop, Self::Pop) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Run {}
+
+impl Hash for Run {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+
+        match self {
+            Self::Push(style) => TextKey::new(style).hash(state),
+            Self::Text(text) => text.hash(state),
+            Self::Placeholder(size) => {
+                size.width.to_bits().hash(state);
+                size.height.to_bits().hash(state);
+            }
+            Self::Pop => {}
+        }
+    }
+}
+
+/// Stroked text is drawn in two passes, the outline below the fill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum TextLayer {
+    /// The glyphs, with the shadows of text that isn't stroked.
+    Fill,
+    /// The outlines and shadows of stroked text, everything else is transparent.
+    Stroke,
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct ParagraphKey {
+    runs: Vec<Run>,
+    width: u32,
+    layer: TextLayer,
+}
+
+/// Fonts and a viewport to lay out nodes with, see [`Node::layout`].
+///
+/// Unlike [`RenderContext`] it has no surface, so layouts can be measured before deciding
+/// the size or the format of the output.
+pub struct LayoutContext {
+    /// The viewport in layout units, whatever the scale of the rendered image.
     pub bounds: Rect,
     collection: FontCollection,
+    fonts: TypefaceFontProvider,
+    paragraphs: RefCell<HashMap<ParagraphKey, Rc<Paragraph>>>,
+    root_font_size: Cell<f32>,
+    /// Whether installed fonts are used along with the registered ones.
+    system_fonts: bool,
 }
 
-impl Context {
+impl LayoutContext {
+    /// Creates a context with the installed fonts, viewport units resolve against `width` × `height`.
+    #[must_use]
     pub fn new(width: f32, height: f32) -> Self {
+        let fonts = TypefaceFontProvider::new();
         let mut collection = FontCollection::new();
 
+        // Registered fonts are looked up before the system ones.
+        collection.set_asset_font_manager(Some(FontMgr::from(fonts.clone())));
         collection.set_default_font_manager(Some(FontMgr::default()), None);
+        collection.enable_font_fallback();
 
         Self {
             bounds: Rect::from_xywh(0.0, 0.0, width, height),
             collection,
+            fonts,
+            paragraphs: RefCell::default(),
+            root_font_size: Cell::new(FontSize::default().size),
+            system_fonts: true,
         }
     }
 
-    pub fn create_paragraph<T: AsRef<str>>(&self, style: &Style, text: T) -> Paragraph {
-        let font_style = FontStyle::new(
-            match style.font_weight {
-                FontWeight::Light => Weight::LIGHT,
-                FontWeight::Normal => Weight::NORMAL,
-                FontWeight::Bold => Weight::BOLD,
+    /// Creates a context that only uses the fonts registered with it, so text is laid out
+    /// the same on every machine. Families that aren't registered use `default_family`
+    /// and characters missing from a font aren't looked up in other fonts.
+    #[must_use]
+    pub fn without_system_fonts(width: f32, height: f32, default_family: &str) -> Self {
+        let mut context = Self::new(width, height);
+
+        context
+            .collection
+            .set_default_font_manager(None::<FontMgr>, Some(default_family));
+        context.collection.disable_font_fallback();
+        context.system_fonts = false;
+
+        context
+    }
+
+    /// Makes `rem` lengths relative to the font size of `root`, the node being laid out.
+    pub(crate) fn set_root(&self, root: &Style) {
+        self.root_font_size
+            .set(root.font_size.unwrap_or_default().size);
+    }
+
+    /// Returns the paragraph made of `runs` laid out at `width`, building it only
+    /// the first time it's asked for since the last [`LayoutContext::clear_paragraphs`].
+    pub(crate) fn paragraph(&self, runs: Vec<Run>, width: f32) -> Rc<Paragraph> {
+        self.cached_paragraph(ParagraphKey {
+            runs,
+            width: width.to_bits(),
+            layer: TextLayer::Fill,
+        })
+    }
+
+    /// Returns the outlines to draw below [`LayoutContext::paragraph`], if any of `runs` is stroked.
+    pub(crate) fn paragraph_stroke(&self, runs: Vec<Run>, width: f32) -> Option<Rc<Paragraph>> {
+        let stroked = runs.iter().any(|run| match run {
+            Run::Push(style) => style.text_stroke.is_some_and(|stroke| stroke.width > 0.0),
+            _ => false,
+        });
+
+        stroked.then(|| {
+            self.cached_paragraph(ParagraphKey {
+                runs,
+                width: width.to_bits(),
+                layer: TextLayer::Stroke,
+            })
+        })
+    }
+
+    /// Makes `paragraph` the one returned for `runs` at `width`, for paragraphs that draw
+    /// the same at another width.
+    pub(crate) fn reuse_paragraph(&self, runs: Vec<Run>, width: f32, paragraph: Rc<Paragraph>) {
+        self.paragraphs.borrow_mut().insert(
+            ParagraphKey {
+                runs,
+                width: width.to_bits(),
+                layer: TextLayer::Fill,
             },
-            Width::NORMAL,
-            match style.font_slant {
+            paragraph,
+        );
+    }
+
+    fn cached_paragraph(&self, key: ParagraphKey) -> Rc<Paragraph> {
+        if let Some(paragraph) = self.paragraphs.borrow().get(&key) {
+            return paragraph.clone();
+        }
+
+        let paragraph =
+            Rc::new(self.build_paragraph(&key.runs, f32::from_bits(key.width), key.layer));
+
+        self.paragraphs.borrow_mut().insert(key, paragraph.clone());
+
+        paragraph
+    }
+
+    /// Drops the cached paragraphs, e.g. between layouts of unrelated nodes.
+    pub fn clear_paragraphs(&self) {
+        self.paragraphs.borrow_mut().clear();
+    }
+
+    fn build_paragraph(&self, runs: &[Run], width: f32, layer: TextLayer) -> Paragraph {
+        let root = runs.iter().find_map(|run| match run {
+            Run::Push(style) => Some(style.as_ref()),
+            _ => None,
+        });
+
+        let mut builder = self.paragraph_builder(root.unwrap_or(&Style::new()), layer);
+
+        for run in runs {
+            match run {
+                Run::Push(style) => {
+                    builder.push_style(&Self::text_style(style, layer));
+                }
+                Run::Text(text) => {
+                    builder.add_text(text);
+                }
+                Run::Placeholder(size) => {
+                    builder.add_placeholder(&PlaceholderStyle::new(
+                        size.width,
+                        size.height,
+                        PlaceholderAlignment::Middle,
+                        TextBaseline::Alphabetic,
+                        0.0,
+                    ));
+                }
+                Run::Pop => {
+                    builder.pop();
+                }
+            }
+        }
+
+        let mut paragraph = builder.build();
+
+        paragraph.layout(width);
+
+        paragraph
+    }
+
+    /// Registers a font from its data, returning the family it can be used as.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FontError::InvalidData`] if `data` isn't a font
+    pub fn register_font(&mut self, data: &[u8], alias: Option<&str>) -> Result<String, FontError> {
+        let typeface = FontMgr::default()
+            .new_from_data(data, None)
+            .ok_or(FontError::InvalidData)?;
+
+        let family = alias.map_or_else(|| typeface.family_name(), ToString::to_string);
+
+        self.fonts.register_typeface(typeface, alias);
+        self.collection.clear_caches();
+        self.clear_paragraphs();
+
+        Ok(family)
+    }
+
+    /// Registers a font file, see [`LayoutContext::register_font`].
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the file can't be read or isn't a font
+    pub fn register_font_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        alias: Option<&str>,
+    ) -> Result<String, FontError> {
+        self.register_font(&fs::read(path)?, alias)
+    }
+
+    /// The typeface of `family`, looked up in the registered fonts before the installed ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FontError::Missing`] if the family is neither registered nor installed
+    pub fn typeface(&self, family: &str) -> Result<Typeface, FontError> {
+        self.fonts
+            .match_family_style(family, FontStyle::normal())
+            .or_else(|| {
+                self.system_fonts
+                    .then(FontMgr::default)?
+                    .match_family_style(family, FontStyle::normal())
+            })
+            .ok_or_else(|| FontError::Missing(family.to_string()))
+    }
+
+    /// Checks that every family of the list is either registered or installed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FontError::Missing`] with the first family that is neither
+    pub fn check_font(&self, family: &FontFamily) -> Result<(), FontError> {
+        family
+            .families()
+            .try_for_each(|name| self.typeface(name).map(drop))
+    }
+
+    /// The text style of a paragraph or span with `style`, for drawing `layer`.
+    fn text_style(style: &Style, layer: TextLayer) -> TextStyle {
+        let font_style = FontStyle::new(
+            Weight::from(i32::from(style.font_weight.unwrap_or_default().value())),
+            Width::from(i32::from(style.font_width.unwrap_or_default().value())),
+            match style.font_slant.unwrap_or_default() {
                 FontSlant::Upright => Slant::Upright,
                 FontSlant::Italic => Slant::Italic,
                 FontSlant::Oblique => Slant::Oblique,
             },
         );
 
+        let font_family = style.font_family.clone().unwrap_or_default();
+
         let mut text_style = TextStyle::new();
 
-        text_style.set_font_families(&[style.font_family.family.as_str()]);
-        text_style.set_font_size(style.font_size.size);
+        text_style.set_font_families(&font_family.families().collect::<Vec<_>>());
+        text_style.set_font_size(style.font_size.unwrap_or_default().size);
         text_style.set_font_style(font_style);
+
+        if let Some(height) = style.line_height {
+            text_style.set_height(height);
+            text_style.set_height_override(true);
+        }
+
+        if let Some(spacing) = style.letter_spacing {
+            text_style.set_letter_spacing(spacing);
+        }
+
+        if let Some(spacing) = style.word_spacing {
+            text_style.set_word_spacing(spacing);
+        }
+
+        let stroke = style.text_stroke.filter(|stroke| stroke.width > 0.0);
+
+        let add_shadows = |text_style: &mut TextStyle| {
+            for shadow in style.text_shadow.iter().flatten() {
+                text_style.add_shadow(TextShadow::new(
+                    skia_safe::Color::new(shadow.color.as_u32()),
+                    (shadow.x, shadow.y),
+                    // Skia blurs by sigma, which is about half the radius.
+                    f64::from(shadow.blur / 2.0),
+                ));
+            }
+        };
+
+        if layer == TextLayer::Stroke {
+            if let Some(stroke) = stroke {
+                let mut paint = Paint::default();
+
+                paint.set_anti_alias(true);
+                paint.set_style(PaintStyle::Stroke);
+                paint.set_stroke_join(PaintJoin::Round);
+                paint.set_stroke_width(stroke.width);
+                paint.set_color(skia_safe::Color::new(stroke.color.as_u32()));
+
+                text_style.set_foreground_paint(&paint);
+
+                add_shadows(&mut text_style);
+            } else {
+                text_style.set_color(skia_safe::Color::TRANSPARENT);
+            }
+
+            return text_style;
+        }
+
         text_style.set_color(skia_safe::Color::new(
             style.color.unwrap_or(Color::from_rgb(0, 0, 0)).as_u32(),
         ));
 
+        if stroke.is_none() {
+            add_shadows(&mut text_style);
+        }
+
+        if let Some(decoration) = style.text_decoration {
+            let mut lines = textlayout::TextDecoration::NO_DECORATION;
+
+            lines.set(textlayout::TextDecoration::UNDERLINE, decoration.underline);
+            lines.set(textlayout::TextDecoration::OVERLINE, decoration.overline);
+            lines.set(
+                textlayout::TextDecoration::LINE_THROUGH,
+                decoration.line_through,
+            );
+
+            text_style.set_decoration_type(lines);
+        }
+
+        text_style
+    }
+
+    fn paragraph_builder(&self, style: &Style, layer: TextLayer) -> ParagraphBuilder {
         let mut paragraph_style = ParagraphStyle::new();
 
-        paragraph_style.set_text_style(&text_style);
+        paragraph_style.set_text_style(&Self::text_style(style, layer));
+        paragraph_style.set_text_align(match style.text_align.unwrap_or_default() {
+            TextAlign::Left => textlayout::TextAlign::Left,
+            TextAlign::Right => textlayout::TextAlign::Right,
+            TextAlign::Center => textlayout::TextAlign::Center,
+            TextAlign::Justify => textlayout::TextAlign::Justify,
+            TextAlign::Start => textlayout::TextAlign::Start,
+            TextAlign::End => textlayout::TextAlign::End,
+        });
+
+        if let Some(lines) = style.max_lines {
+            paragraph_style.set_max_lines(lines.max(1));
+            paragraph_style.set_ellipsis("\u{2026}");
+        }
+
+        ParagraphBuilder::new(&paragraph_style, &self.collection)
+    }
+}
+
+impl Environment for LayoutContext {
+    fn viewport(&self) -> Size {
+        self.bounds.size
+    }
+
+    fn root_font_size(&self) -> f32 {
+        self.root_font_size.get()
+    }
+}
+
+/// How the surface size follows the rendered content.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SurfaceSizing {
+    /// The surface keeps the size it was created with.
+    #[default]
+    Fixed,
+    /// The root is measured with an unbounded height and the surface gets its height, up to `max` layout units.
+    AutoHeight { max: i16 },
+    /// The root is measured with an unbounded width and the surface gets its width, up to `max` layout units.
+    AutoWidth { max: i16 },
+}
 
-        let mut builder = ParagraphBuilder::new(&paragraph_style, &self.collection);
+impl SurfaceSizing {
+    fn constraints(self, size: Size) -> Constraints {
+        Constraints::loose(match self {
+            Self::Fixed => size,
+            Self::AutoHeight { .. } => Size::new(size.width, f32::INFINITY),
+            Self::AutoWidth { .. } => Size::new(f32::INFINITY, size.height),
+        })
+    }
 
-        builder.add_text(text.as_ref());
+    fn surface_size(self, size: Size, content: Size) -> Size {
+        let clamp = |value: f32, max: i16| value.ceil().clamp(1.0, f32::from(max.max(1)));
 
-        builder.build()
+        match self {
+            Self::Fixed => size,
+            Self::AutoHeight { max } => Size::new(size.width, clamp(content.height, max)),
+            Self::AutoWidth { max } => Size::new(clamp(content.width, max), size.height),
+        }
     }
 }
 
 pub struct RenderContext {
     surface: Surface,
-    context: Context,
+    context: LayoutContext,
+    stylesheet: Stylesheet,
+    sizing: SurfaceSizing,
+    scale: f32,
+    print_layout: bool,
+    debug_overlay: bool,
 }
 
 impl RenderContext {
     #[must_use]
     pub fn new(width: i16, height: i16) -> Option<Self> {
+        Self::from_layout_context(LayoutContext::new(f32::from(width), f32::from(height)))
+    }
+
+    /// Creates a context drawing with the fonts of `context`, the surface takes the size of its viewport.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn from_layout_context(context: LayoutContext) -> Option<Self> {
+        let size = context.bounds.size;
+
         Some(Self {
-            surface: surfaces::raster_n32_premul((i32::from(width), i32::from(height)))?,
-            context: Context::new(f32::from(width), f32::from(height)),
+            surface: surfaces::raster_n32_premul((
+                (size.width.ceil() as i32).max(1),
+                (size.height.ceil() as i32).max(1),
+            ))?,
+            context,
+            stylesheet: Stylesheet::new(),
+            sizing: SurfaceSizing::Fixed,
+            scale: 1.0,
+            print_layout: false,
+            debug_overlay: false,
         })
     }
 
-    pub fn render(&mut self, node: &Node) {
+    /// Creates a context whose surface grows to the height of the rendered content, up to `max_height`.
+    ///
+    /// Viewport units resolve against `width` × `height`.
+    #[must_use]
+    pub fn auto_height(width: i16, height: i16, max_height: i16) -> Option<Self> {
+        Self::new(width, height)
+            .map(|context| context.with_sizing(SurfaceSizing::AutoHeight { max: max_height }))
+    }
+
+    #[must_use]
+    pub const fn with_sizing(mut self, sizing: SurfaceSizing) -> Self {
+        self.sizing = sizing;
+
+        self
+    }
+
+    /// Renders with `scale` pixels per layout unit, e.g. `2.0` for a 2x image of the same layout.
+    ///
+    /// Sizes, viewport units and SVG and PDF output stay in layout units.
+    #[must_use]
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = if scale.is_finite() && scale > 0.0 {
+            scale
+        } else {
+            1.0
+        };
+
+        if let Some(surface) = surfaces::raster_n32_premul(self.pixels(self.context.bounds.size)) {
+            self.surface = surface;
+        }
+
+        self
+    }
+
+    /// Prints the layout of every render as JSON, see [`LayoutTree::to_json`].
+    #[must_use]
+    pub const fn with_print_layout(mut self, print_layout: bool) -> Self {
+        self.print_layout = print_layout;
+
+        self
+    }
+
+    /// Draws every node with its margin and padding areas shaded, its border and content boxes
+    /// outlined and a label with its kind and size.
+    #[must_use]
+    pub const fn with_debug_overlay(mut self, debug_overlay: bool) -> Self {
+        self.debug_overlay = debug_overlay;
+
+        self
+    }
+
+    /// The fonts and viewport nodes are laid out with, e.g. to measure a node before rendering it.
+    #[must_use]
+    pub const fn layout_context(&self) -> &LayoutContext {
+        &self.context
+    }
+
+    #[must_use]
+    pub fn with_stylesheet(mut self, stylesheet: Stylesheet) -> Self {
+        self.stylesheet = stylesheet;
+
+        self
+    }
+
+    /// Adds rules applied to every rendered node, later rules win over earlier ones of equal specificity.
+    pub fn add_stylesheet(&mut self, stylesheet: Stylesheet) {
+        self.stylesheet.extend(stylesheet);
+    }
+
+    /// Parses `style { ... }` blocks of the layout language and adds their rules,
+    /// see [`RenderContext::add_stylesheet`].
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the source isn't made of valid `style` blocks
+    pub fn add_stylesheet_source(&mut self, source: &str, theme: &Theme) -> Result<(), ParseError> {
+        self.add_stylesheet(parse_stylesheet_source(source, theme)?);
+
+        Ok(())
+    }
+
+    /// Registers a font from its data, under `alias` if given, otherwise under its own family name.
+    /// Registered fonts take precedence over the installed ones.
+    ///
+    /// Returns the family to use in `font_family`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FontError::InvalidData`] if `data` isn't a font
+    pub fn register_font(&mut self, data: &[u8], alias: Option<&str>) -> Result<String, FontError> {
+        self.context.register_font(data, alias)
+    }
+
+    /// Registers a font file, see [`RenderContext::register_font`].
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the file can't be read or isn't a font
+    pub fn register_font_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        alias: Option<&str>,
+    ) -> Result<String, FontError> {
+        self.context.register_font_file(path, alias)
+    }
+
+    /// Checks the font families used by `node` and its descendants, including ones set by stylesheets.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first family that is neither registered nor installed.
+    pub fn check_fonts(&self, node: &Node) -> Result<(), FontError> {
+        fn check(context: &LayoutContext, node: &Node) -> Result<(), FontError> {
+            if let Some(family) = &node.get_style().font_family {
+                context.check_font(family)?;
+            }
+
+            node.element()
+                .children()
+                .iter()
+                .try_for_each(|child| check(context, child))
+        }
+
+        let mut node = node.clone();
+
+        node.apply_stylesheet(&self.stylesheet);
+
+        check(&self.context, &node)
+    }
+
+    /// Renders `node`, returning it along with its layout, e.g. to find where a node was drawn.
+    pub fn render(&mut self, node: &Node) -> LayoutTree {
+        let (node, measure_node, size) = self.layout(node);
+
+        self.draw(
+            &node,
+            measure_node.clone(),
+            Rect::new(Point::default(), size),
+        );
+
+        let tree = LayoutTree::new(node, measure_node);
+
+        if self.print_layout {
+            println!("{}", tree.to_json());
+        }
+
+        tree
+    }
+
+    /// Renders the part of `node` inside `region` at full resolution, the surface takes the size of `region`.
+    ///
+    /// `node` is laid out as by [`RenderContext::render`], so a tall layout can be split into pages.
+    pub fn render_region(&mut self, node: &Node, region: Rect) {
+        let (node, measure_node, _) = self.layout(node);
+
+        self.draw(&node, measure_node, region);
+    }
+
+    /// Renders the first node with the given id on its own, the surface takes the size of its border box.
+    ///
+    /// `node` is laid out as a whole, but only the found node and its descendants are drawn,
+    /// without the transforms of its ancestors.
+    ///
+    /// Returns `false`, leaving the surface untouched, if no node has the id.
+    pub fn render_id(&mut self, node: &Node, id: &str) -> bool {
+        let (node, measure_node, _) = self.layout(node);
+
+        let tree = LayoutTree::new(node, measure_node);
+
+        let Some((node, measure_node)) = tree.find_by_id(id) else {
+            return false;
+        };
+
+        self.draw(node, measure_node.clone(), measure_node.outer);
+
+        true
+    }
+
+    /// Draws the part of a laid out node inside `region`, reallocating the surface to its size.
+    fn draw(&mut self, node: &Node, measure_node: MeasureNode, region: Rect) {
+        let size = self.pixels(region.size);
+
+        if size != (self.surface.width(), self.surface.height()) {
+            if let Some(surface) = surfaces::raster_n32_premul(size) {
+                self.surface = surface;
+            }
+        }
+
         let canvas = self.surface.canvas();
 
-        let measure_node = node.measure(&self.context, &self.context.bounds);
+        canvas.clear(skia_safe::Color::TRANSPARENT);
+        canvas.save();
+        canvas.scale((self.scale, self.scale));
+        canvas.translate((-region.origin.x, -region.origin.y));
 
-        println!("{measure_node:#?}");
+        let overlay = self.debug_overlay.then(|| measure_node.clone());
 
         node.draw(canvas, &self.context, measure_node);
+
+        if let Some(layout) = overlay {
+            debug::draw_overlay(canvas, node, &layout);
+        }
+
+        canvas.restore();
+    }
+
+    /// Renders `frames` frames of the tree `frame` returns for a time going from 0 on the first frame
+    /// to 1 on the last, each shown for `delay`, and encodes them as an animation.
+    ///
+    /// Styles can follow the time with [`Keyframes`](crate::styling::Keyframes).
+    ///
+    /// # Errors
+    ///
+    /// Returns error if there are no frames, their sizes differ or they failed to encode
+    pub fn render_animation<F: FnMut(f32) -> Node>(
+        &mut self,
+        format: AnimationFormat,
+        frames: u16,
+        delay: Duration,
+        mut frame: F,
+    ) -> Result<Vec<u8>, AnimationError> {
+        let mut pixels = Vec::with_capacity(usize::from(frames));
+        let mut size = None;
+
+        for index in 0..frames {
+            let time = if frames > 1 {
+                f32::from(index) / f32::from(frames - 1)
+            } else {
+                0.0
+            };
+
+            self.render(&frame(time));
+
+            let dimensions = (self.surface.width(), self.surface.height());
+
+            if *size.get_or_insert(dimensions) != dimensions {
+                return Err(AnimationError::FrameSize);
+            }
+
+            pixels.push(self.read_pixels().ok_or(AnimationError::Pixels)?);
+        }
+
+        let (width, height) = size.ok_or(AnimationError::Empty)?;
+
+        animation::encode(
+            format,
+            (width.unsigned_abs(), height.unsigned_abs()),
+            &mut pixels,
+            delay,
+        )
+    }
+
+    /// Renders `node` to an SVG document of the size the surface would have.
+    ///
+    /// Text is converted to paths, so the document doesn't depend on the fonts of its viewer.
+    #[must_use]
+    pub fn render_svg(&self, node: &Node) -> Vec<u8> {
+        let (node, measure_node, size) = self.layout(node);
+
+        let canvas = svg::Canvas::new(
+            SkRect::from_wh(size.width, size.height),
+            svg::canvas::Flags::CONVERT_TEXT_TO_PATHS,
+        );
+
+        node.draw(&canvas, &self.context, measure_node);
+
+        canvas.end().as_bytes().to_vec()
+    }
+
+    /// Renders `node` to a single page PDF document of the size the surface would have,
+    /// a pixel becomes a point.
+    #[must_use]
+    pub fn render_pdf(&self, node: &Node) -> Vec<u8> {
+        let (node, measure_node, size) = self.layout(node);
+
+        let mut data = Vec::new();
+        let mut document =
+            pdf::new_document(&mut data, None).begin_page((size.width, size.height), None);
+
+        node.draw(document.canvas(), &self.context, measure_node);
+
+        document.end_page().close();
+
+        data
+    }
+
+    /// Applies the stylesheet to `node` and lays it out, returning the styled tree,
+    /// its layout and the size of the output in layout units.
+    ///
+    /// Unless the sizing is fixed, the output takes the measured size, content past the maximum size is clipped.
+    fn layout(&self, node: &Node) -> (Node, MeasureNode, Size) {
+        let mut node = node.clone();
+
+        node.apply_stylesheet(&self.stylesheet);
+        node.inherit_styles();
+
+        self.context.clear_paragraphs();
+        self.context.set_root(node.get_style());
+
+        let bounds = self.context.bounds;
+
+        if self.sizing == SurfaceSizing::Fixed {
+            let measure_node = Measurer::layout(&node, &self.context, bounds);
+
+            return (node, measure_node, bounds.size);
+        }
+
+        let mut measure_node = node.measure(&self.context, self.sizing.constraints(bounds.size));
+
+        measure_node.set_position(node.get_style(), Point::default());
+        measure_node.arrange(bounds.origin);
+
+        let size = self
+            .sizing
+            .surface_size(bounds.size, measure_node.margin_size(node.get_style()));
+
+        (node, measure_node, size)
+    }
+
+    /// Converts a size in layout units to the pixel size of the surface.
+    #[allow(clippy::cast_possible_truncation)]
+    fn pixels(&self, size: Size) -> (i32, i32) {
+        (
+            ((size.width * self.scale).ceil() as i32).max(1),
+            ((size.height * self.scale).ceil() as i32).max(1),
+        )
+    }
+
+    /// Reads the surface as unpremultiplied RGBA pixels.
+    fn read_pixels(&mut self) -> Option<Vec<u8>> {
+        let info = ImageInfo::new(
+            (self.surface.width(), self.surface.height()),
+            ColorType::RGBA8888,
+            AlphaType::Unpremul,
+            None,
+        );
+
+        let mut pixels = vec![0; info.compute_min_byte_size()];
+
+        self.surface
+            .read_pixels(&info, &mut pixels, info.min_row_bytes(), (0, 0))
+            .then_some(pixels)
     }
 
     pub fn encode(&mut self) -> Option<Vec<u8>> {
+        self.encode_as(EncodedImageFormat::PNG, 100)
+    }
+
+    /// Encodes the surface as PNG, JPEG or WebP, `quality` goes from 0 to 100 and is ignored by PNG.
+    ///
+    /// WebP is lossless at a quality of 100.
+    pub fn encode_as(&mut self, format: EncodedImageFormat, quality: u32) -> Option<Vec<u8>> {
         let image = self.surface.image_snapshot();
 
         image
-            .encode(None, EncodedImageFormat::PNG, 100)
+            .encode(None, format, quality.min(100))
             .map(|data| data.to_vec())
     }
 }
@@ -2,11 +2,21 @@
 #![allow(clippy::module_name_repetitions, clippy::cast_precision_loss)]
 
 use self::styling::Length;
-pub use self::{elements::*, graphics::RenderContext, node::*};
-
+pub use self::{
+    animation::{AnimationError, AnimationFormat},
+    elements::*,
+    graphics::{FontError, LayoutContext, RenderContext, SurfaceSizing},
+    node::*,
+};
+pub use skia_safe::EncodedImageFormat;
+
+mod animation;
+mod debug;
 mod elements;
 mod graphics;
 mod node;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
 pub mod geometry {
     pub use muzui_geometry::*;
@@ -16,6 +26,10 @@ pub mod layout {
     pub use muzui_layout::*;
 }
 
+pub mod lang {
+    pub use muzui_lang::*;
+}
+
 pub mod macros {
     pub use muzui_macros::*;
 }
@@ -26,8 +40,9 @@ pub mod styling {
 
 pub mod prelude {
     pub use crate::{
-        layout::*, macros::*, styling::*, FloatExt, FloatGridLengthExt, FloatLengthExt, Node,
-        RenderContext,
+        layout::*, macros::*, styling::*, AnimationFormat, EncodedImageFormat, FloatExt,
+        FloatGridLengthExt, FloatLengthExt, LayoutContext, LayoutTree, Node, RenderContext,
+        SurfaceSizing,
     };
 }
 
@@ -37,6 +52,12 @@ pub trait FloatExt<T> {
 
 pub trait FloatLengthExt {
     fn percent(self) -> Length;
+    fn vw(self) -> Length;
+    fn vh(self) -> Length;
+    fn vmin(self) -> Length;
+    fn vmax(self) -> Length;
+    fn em(self) -> Length;
+    fn rem(self) -> Length;
 }
 
 pub trait FloatGridLengthExt {
@@ -59,6 +80,30 @@ impl FloatLengthExt for i32 {
     fn percent(self) -> Length {
         Length::Percent(self as f32)
     }
+
+    fn vw(self) -> Length {
+        Length::Vw(self as f32)
+    }
+
+    fn vh(self) -> Length {
+        Length::Vh(self as f32)
+    }
+
+    fn vmin(self) -> Length {
+        Length::Vmin(self as f32)
+    }
+
+    fn vmax(self) -> Length {
+        Length::Vmax(self as f32)
+    }
+
+    fn em(self) -> Length {
+        Length::Em(self as f32)
+    }
+
+    fn rem(self) -> Length {
+        Length::Rem(self as f32)
+    }
 }
 
 impl FloatGridLengthExt for i32 {
@@ -83,6 +128,30 @@ impl FloatLengthExt for f32 {
     fn percent(self) -> Length {
         Length::Percent(self)
     }
+
+    fn vw(self) -> Length {
+        Length::Vw(self)
+    }
+
+    fn vh(self) -> Length {
+        Length::Vh(self)
+    }
+
+    fn vmin(self) -> Length {
+        Length::Vmin(self)
+    }
+
+    fn vmax(self) -> Length {
+        Length::Vmax(self)
+    }
+
+    fn em(self) -> Length {
+        Length::Em(self)
+    }
+
+    fn rem(self) -> Length {
+        Length::Rem(self)
+    }
 }
 
 impl FloatGridLengthExt for f32 {
@@ -93,11 +162,11 @@ impl FloatGridLengthExt for f32 {
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
+    use std::{env, fs, process};
 
     use crate as muzui;
     use material_colors::{color::Argb, scheme::Scheme, theme::ThemeBuilder};
-    use muzui::prelude::*;
+    use muzui::{lang::style::Theme, prelude::*, testing};
 
     trait ArgbExt {
         fn as_color(&self) -> Color;
@@ -109,6 +178,44 @@ mod tests {
         }
     }
 
+    fn scheme(source: u32) -> Scheme {
+        ThemeBuilder::with_source(Argb::from_u32(source))
+            .build()
+            .schemes
+            .dark
+    }
+
+    fn theme(scheme: Scheme) -> Theme {
+        scheme
+            .into_iter()
+            .map(|(name, color)| (name, color.as_color()))
+            .collect()
+    }
+
+    /// A small PNG standing in for pictures.
+    fn picture() -> Vec<u8> {
+        let mut context = testing::render_context(64, 64).unwrap();
+
+        context.render(
+            &Node::column()
+                .size(100.percent())
+                .background(0xFF20_60C0)
+                .corner_radius(16.0)
+                .build(),
+        );
+
+        context.encode().unwrap()
+    }
+
+    /// Writes [`picture`] to a file for layouts, which load images by path.
+    fn picture_file(name: &str) -> String {
+        let path = env::temp_dir().join(format!("muzui-{name}-{}.png", process::id()));
+
+        fs::write(&path, picture()).unwrap();
+
+        path.to_string_lossy().into_owned()
+    }
+
     #[test]
     fn test_macros() {
         #[allow(non_snake_case)]
@@ -143,6 +250,7 @@ mod tests {
     }
 
     #[test]
+    #[ignore = "needs tests/snapshots/graphics.png, record it with MUZUI_UPDATE_SNAPSHOTS=1"]
     fn test_graphics() {
         #[allow(non_snake_case, clippy::needless_pass_by_value)]
         fn Cell(style: Style, theme: &Scheme) -> Node {
@@ -157,7 +265,7 @@ mod tests {
                     corner_radius: 24.0,
 
                     Text("Hello World") {
-                        font_family: "JetBrainsMono Nerd Font Mono",
+                        font_family: testing::MONO,
                         color: theme.on_primary_container.as_u32(),
                     }
                 }
@@ -193,12 +301,263 @@ mod tests {
             }
         };
 
-        let mut context = RenderContext::new(600, 100).unwrap();
+        let mut context = testing::render_context(600, 100).unwrap();
+
+        testing::Snapshots::new().assert("graphics", &mut context, &node);
+    }
+
+    #[test]
+    #[ignore = "needs tests/snapshots/measure.png, record it with MUZUI_UPDATE_SNAPSHOTS=1"]
+    fn test_measure() {
+        let text = |data: &str| {
+            Node::text(data)
+                .background(0xFF00_FF00)
+                .color(0xFF00_00FF)
+                .font_family(testing::SANS)
+                .font_size(32.0)
+                .font_weight(FontWeight::Bold)
+                .build()
+        };
+
+        let node = Node::column()
+            .padding(8.0)
+            .background(0xFFFF_0000)
+            .spacing(8.0)
+            .width(50.percent())
+            .child(
+                Node::row()
+                    .height(px(200.0))
+                    .width(100.percent())
+                    .background(0xFF00_FF00)
+                    .corner_radius(50.0)
+                    .child(
+                        Node::row()
+                            .height(50.percent())
+                            .width(50.percent())
+                            .background(0xFF00_00FF)
+                            .corner_radius(50.0)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .child(
+                Node::image("picture", picture())
+                    .size(px(200.0))
+                    .position(Position::Absolute)
+                    .x(100.0)
+                    .y(50.0)
+                    .background(0xFF00_FF00)
+                    .corner_radius(50.0)
+                    .build(),
+            )
+            .child(
+                Node::row()
+                    .size(px(200.0))
+                    .background(0xFF00_FF00)
+                    .corner_radius(50.0)
+                    .build(),
+            )
+            .child(text("Привет, мир"))
+            .child(text("Привет, мир, это строка подлиннее"))
+            .build();
+
+        let mut context = testing::render_context(1000, 1000).unwrap();
+
+        testing::Snapshots::new().assert("measure", &mut context, &node);
+    }
 
-        context.render(&node);
+    #[test]
+    #[ignore = "needs tests/snapshots/masonry.png, record it with MUZUI_UPDATE_SNAPSHOTS=1"]
+    fn test_masonry() {
+        let theme = scheme(0xFF00_FF00);
+        let heights = [200.0, 100.0, 150.0, 175.0, 75.0];
+
+        let node = Node::masonry(100.0)
+            .padding(8.0)
+            .background(theme.surface_container_high.as_color())
+            .spacing(8.0)
+            .width(100.percent())
+            .height(100.percent())
+            .children(
+                (0..25)
+                    .map(|index| {
+                        Node::column()
+                            .height(px(heights[index % 5]))
+                            .width(100.percent())
+                            .background(theme.surface_bright.as_color())
+                            .corner_radius(12.0)
+                            .padding(4.0)
+                            .child(
+                                Node::column()
+                                    .background(theme.primary_container.as_color())
+                                    .size(100.percent())
+                                    .corner_radius(12.0)
+                                    .build(),
+                            )
+                            .build()
+                    })
+                    .collect(),
+            )
+            .build();
+
+        let mut context = testing::render_context(1000, 1000).unwrap();
+
+        testing::Snapshots::new().assert("masonry", &mut context, &node);
+    }
 
-        if let Some(data) = context.encode() {
-            fs::write("./image.png", data).unwrap();
-        }
+    #[test]
+    #[ignore = "needs tests/snapshots/language.png, record it with MUZUI_UPDATE_SNAPSHOTS=1"]
+    fn test_language() {
+        let data = serde_json::json!({
+            "greeting": "Привет",
+            "sub": { "name": "мир" },
+            "picture": picture_file("language"),
+        });
+
+        let node = Node::parse(
+            r#"Column {
+                padding: 8,
+                background: theme.surface_container,
+                spacing: 8,
+                width: 50%,
+
+                Row {
+                    height: 200,
+                    width: 100%,
+                    background: theme.primary_container,
+                    corner-radius: 50,
+
+                    Row {
+                        height: 50%,
+                        width: 50%,
+                        background: theme.tertiary_container,
+                        corner-radius: 50,
+                    }
+                }
+
+                Row {
+                    height: 200,
+                    width: 200,
+                    background: theme.primary_container,
+                    corner-radius: 50
+                }
+
+                Text("{greeting}, {sub.name}") {
+                    background: theme.primary_container,
+                    color: theme.on_primary_container,
+                    font-family: "DejaVu Sans Mono",
+                    font-size: 32,
+                    font-weight: bold
+                }
+
+                Image(picture) {
+                    height: 200,
+                    width: 200,
+                    position: absolute,
+                    x: 100,
+                    y: 50,
+                    corner-radius: 50
+                }
+            }"#,
+            &theme(scheme(0xFFFF_0000)),
+            &data,
+        )
+        .expect("failed to parse layout");
+
+        let mut context = testing::render_context(1000, 1000).unwrap();
+
+        testing::Snapshots::new().assert("language", &mut context, &node);
+    }
+
+    #[test]
+    #[ignore = "needs tests/snapshots/profile.png, record it with MUZUI_UPDATE_SNAPSHOTS=1"]
+    fn test_profile() {
+        let data = serde_json::json!({
+            "user_info": {
+                "user_info": { "nickname": "Traveler", "introduce": "Exploring Teyvat" },
+            },
+            "characters": {
+                "list": [{
+                    "weapon": {
+                        "name": "Skyward Harp",
+                        "desc": "A bow that sings of the sky and the wind.",
+                    },
+                }],
+            },
+            "avatar": picture_file("profile"),
+        });
+
+        let node = Node::parse(
+            r#"Column {
+                padding: 96 96 96 0,
+                spacing: 48,
+                background: theme.surface_container,
+                width: 100%,
+                height: 100%,
+
+                Row {
+                    spacing: 48,
+
+                    Column {
+                        padding: 320 0 48 0,
+                        background: theme.surface_container_highest,
+                        corner-radius: 48,
+
+                        Text("{user_info.user_info.nickname}") {
+                            background: theme.primary_container,
+                            color: theme.primary,
+                            font-size: 96,
+                        }
+                    }
+
+                    Column {
+                        padding: 0 48,
+                        background: theme.surface_container_highest,
+                        corner-radius: 48,
+
+                        Text("{user_info.user_info.introduce}") {
+                            color: theme.primary,
+                            font-size: 96,
+                        }
+                    }
+                }
+
+                Column {
+                    background: theme.surface_container_highest,
+                    width: 100%,
+                    height: 100%,
+                    corner-radius: 72 72 0 0,
+                    padding: 12 120 12 12,
+
+                    Text("{characters.list[0].weapon.name}") {
+                        background: theme.primary_container,
+                        color: theme.primary,
+                        font-weight: bold,
+                        font-size: 80
+                    }
+
+                    Text("{characters.list[0].weapon.desc}") {
+                        color: theme.primary,
+                        font-size: 72
+                    }
+                }
+
+                Image(avatar) {
+                    position: absolute,
+                    corner-radius: 72,
+                    width: 320,
+                    height: 320,
+                    x: 48,
+                    y: 48
+                }
+            }"#,
+            &theme(scheme(0xFF00_FF00)),
+            &data,
+        )
+        .expect("failed to parse layout");
+
+        let mut context = testing::render_context(1920, 1080).unwrap();
+
+        testing::Snapshots::new().assert("profile", &mut context, &node);
     }
 }
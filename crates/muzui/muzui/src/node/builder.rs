@@ -1,8 +1,11 @@
 use crate::{
+    lang::grid::GridAttributes,
     styling::{
-        Color, FontFamily, FontSize, FontSlant, FontWeight, Length, Position, Style, Thickness,
+        Color, FontFamily, FontSize, FontSlant, FontWeight, FontWidth, Length, Position, Style,
+        TextAlign, TextDecoration, TextShadow, TextStroke, TextTransform, Thickness,
+        TransformOrigin, Vector,
     },
-    Column, Element, GridLength, Node, Row,
+    Column, Element, GridFlow, GridLength, Node, Row,
 };
 
 pub struct NodeBuilder {
@@ -25,7 +28,8 @@ impl NodeBuilder {
             Element::Container(element) => element.children.push(node),
             Element::Masonry(element) => element.children.push(node),
             Element::Grid(element) => element.children.push(node),
-            _ => {}
+            Element::Text(element) => element.children.push(node),
+            Element::Image(_) => {}
         }
 
         self
@@ -37,7 +41,8 @@ impl NodeBuilder {
             Element::Container(element) => element.children.extend(nodes),
             Element::Masonry(element) => element.children.extend(nodes),
             Element::Grid(element) => element.children.extend(nodes),
-            _ => {}
+            Element::Text(element) => element.children.extend(nodes),
+            Element::Image(_) => {}
         }
 
         self
@@ -65,30 +70,96 @@ impl NodeBuilder {
         self
     }
 
+    /// Names the areas of a grid, one string per row with a name for every column, e.g.
+    /// `["header header", "sidebar main"]`. Use `.` for cells outside of any area.
+    #[must_use]
+    pub fn areas<T: IntoIterator<Item = S>, S: AsRef<str>>(mut self, rows: T) -> Self {
+        if let Element::Grid(element) = &mut self.element {
+            element.areas.extend(rows.into_iter().map(|row| {
+                row.as_ref()
+                    .split_whitespace()
+                    .map(ToString::to_string)
+                    .collect()
+            }));
+        }
+
+        self
+    }
+
+    #[must_use]
+    pub fn flow(mut self, value: GridFlow) -> Self {
+        if let Element::Grid(element) = &mut self.element {
+            element.flow = value;
+        }
+
+        self
+    }
+
+    /// Applies the attributes of a grid parsed from a layout, keeping the ones it leaves unset.
+    #[must_use]
+    pub fn grid_attributes(mut self, attributes: GridAttributes) -> Self {
+        if let Element::Grid(grid) = &mut self.element {
+            grid.flow = attributes.flow.unwrap_or(grid.flow);
+            grid.row_gap = attributes.row_gap.unwrap_or(grid.row_gap);
+            grid.column_gap = attributes.column_gap.unwrap_or(grid.column_gap);
+
+            if let Some(areas) = attributes.areas {
+                grid.areas = areas;
+            }
+        }
+
+        self
+    }
+
     #[must_use]
-    pub const fn row(mut self, value: usize) -> Self {
-        self.style.row = value;
+    pub fn auto_rows(mut self, value: GridLength) -> Self {
+        if let Element::Grid(element) = &mut self.element {
+            element.auto_rows = value;
+        }
+
+        self
+    }
+
+    #[must_use]
+    pub fn auto_columns(mut self, value: GridLength) -> Self {
+        if let Element::Grid(element) = &mut self.element {
+            element.auto_columns = value;
+        }
+
+        self
+    }
+
+    #[must_use]
+    pub fn area<T: Into<String>>(mut self, value: T) -> Self {
+        self.style.area = Some(value.into());
+
+        self
+    }
+
+    #[must_use]
+    pub fn row<T: Into<Option<usize>>>(mut self, value: T) -> Self {
+        self.style.row = value.into();
 
         self
     }
 
     #[must_use]
     pub const fn row_span(mut self, value: usize) -> Self {
-        self.style.row_span = value;
+        self.style.row_span = Some(value);
 
         self
     }
 
     #[must_use]
-    pub const fn column(mut self, value: usize) -> Self {
-        self.style.column = value;
+    pub fn column<T: Into<Option<usize>>>(mut self, value: T) -> Self {
+        self.style.column = value.into();
 
         self
     }
 
     #[must_use]
     pub const fn column_span(mut self, value: usize) -> Self {
-        self.style.column_span = value;
+        self.style.column_span = Some(value);
 
         self
     }
@@ -98,30 +169,78 @@ impl NodeBuilder {
         match &mut self.element {
             Element::Container(container) => container.spacing = value,
             Element::Masonry(masonry) => masonry.spacing = value,
-            Element::Grid(grid) => grid.spacing = value,
+            Element::Grid(grid) => {
+                grid.row_gap = value;
+                grid.column_gap = value;
+            }
             _ => {}
         }
 
         self
     }
 
+    #[must_use]
+    pub fn row_gap(mut self, value: f32) -> Self {
+        if let Element::Grid(grid) = &mut self.element {
+            grid.row_gap = value;
+        }
+
+        self
+    }
+
+    #[must_use]
+    pub fn column_gap(mut self, value: f32) -> Self {
+        if let Element::Grid(grid) = &mut self.element {
+            grid.column_gap = value;
+        }
+
+        self
+    }
+
+    #[must_use]
+    pub fn min_columns(mut self, value: usize) -> Self {
+        if let Element::Masonry(masonry) = &mut self.element {
+            masonry.min_columns = value;
+        }
+
+        self
+    }
+
+    #[must_use]
+    pub fn max_columns<T: Into<Option<usize>>>(mut self, value: T) -> Self {
+        if let Element::Masonry(masonry) = &mut self.element {
+            masonry.max_columns = value.into();
+        }
+
+        self
+    }
+
+    #[must_use]
+    pub fn preserve_order(mut self, value: bool) -> Self {
+        if let Element::Masonry(masonry) = &mut self.element {
+            masonry.preserve_order = value;
+        }
+
+        self
+    }
+
     #[must_use]
     pub fn margin<T: Into<Thickness>>(mut self, value: T) -> Self {
-        self.style.margin = value.into();
+        self.style.margin = Some(value.into());
 
         self
     }
 
     #[must_use]
     pub fn padding<T: Into<Thickness>>(mut self, value: T) -> Self {
-        self.style.padding = value.into();
+        self.style.padding = Some(value.into());
 
         self
     }
 
     #[must_use]
     pub fn corner_radius<T: Into<Thickness>>(mut self, value: T) -> Self {
-        self.style.corner_radius = value.into();
+        self.style.corner_radius = Some(value.into());
 
         self
     }
@@ -140,30 +259,107 @@ impl NodeBuilder {
         self
     }
 
+    /// Takes a single family or a comma separated fallback list, e.g. `"Inter, Noto Color Emoji"`.
     #[must_use]
     pub fn font_family<T: Into<String>>(mut self, family: T) -> Self {
-        self.style.font_family = FontFamily::new(family);
+        self.style.font_family = Some(FontFamily::new(family));
 
         self
     }
 
     #[must_use]
-    pub fn font_size(mut self, size: f32) -> Self {
-        self.style.font_size = FontSize::new(size);
+    pub const fn font_size(mut self, size: f32) -> Self {
+        self.style.font_size = Some(FontSize::new(size));
 
         self
     }
 
+    /// Takes a [`FontWeight`] or a numeric weight, e.g. `600`.
     #[must_use]
-    pub const fn font_weight(mut self, value: FontWeight) -> Self {
-        self.style.font_weight = value;
+    pub fn font_weight<T: Into<FontWeight>>(mut self, value: T) -> Self {
+        self.style.font_weight = Some(value.into());
 
         self
     }
 
     #[must_use]
     pub const fn font_slant(mut self, value: FontSlant) -> Self {
-        self.style.font_slant = value;
+        self.style.font_slant = Some(value);
+
+        self
+    }
+
+    #[must_use]
+    pub const fn font_width(mut self, value: FontWidth) -> Self {
+        self.style.font_width = Some(value);
+
+        self
+    }
+
+    #[must_use]
+    pub const fn text_align(mut self, value: TextAlign) -> Self {
+        self.style.text_align = Some(value);
+
+        self
+    }
+
+    /// Height of a line as a multiple of the font size.
+    #[must_use]
+    pub const fn line_height(mut self, value: f32) -> Self {
+        self.style.line_height = Some(value);
+
+        self
+    }
+
+    #[must_use]
+    pub const fn letter_spacing(mut self, value: f32) -> Self {
+        self.style.letter_spacing = Some(value);
+
+        self
+    }
+
+    #[must_use]
+    pub const fn word_spacing(mut self, value: f32) -> Self {
+        self.style.word_spacing = Some(value);
+
+        self
+    }
+
+    #[must_use]
+    pub const fn text_decoration(mut self, value: TextDecoration) -> Self {
+        self.style.text_decoration = Some(value);
+
+        self
+    }
+
+    /// Adds a shadow, shadows added earlier are drawn on top.
+    #[must_use]
+    pub fn text_shadow(mut self, value: TextShadow) -> Self {
+        self.style
+            .text_shadow
+            .get_or_insert_with(Vec::new)
+            .push(value);
+
+        self
+    }
+
+    #[must_use]
+    pub fn text_stroke<T: Into<Color>>(mut self, width: f32, color: T) -> Self {
+        self.style.text_stroke = Some(TextStroke::new(width, color));
+
+        self
+    }
+
+    #[must_use]
+    pub const fn text_transform(mut self, value: TextTransform) -> Self {
+        self.style.text_transform = Some(value);
+
+        self
+    }
+
+    #[must_use]
+    pub const fn max_lines(mut self, value: usize) -> Self {
+        self.style.max_lines = Some(value);
 
         self
     }
@@ -189,7 +385,7 @@ impl NodeBuilder {
 
     #[must_use]
     pub const fn position(mut self, value: Position) -> Self {
-        self.style.position = value;
+        self.style.position = Some(value);
 
         self
     }
@@ -208,6 +404,74 @@ impl NodeBuilder {
         self
     }
 
+    #[must_use]
+    pub fn translate<T: Into<Vector>>(mut self, value: T) -> Self {
+        self.style.translate = Some(value.into());
+
+        self
+    }
+
+    /// Rotates clockwise by `value` degrees.
+    #[must_use]
+    pub const fn rotate(mut self, value: f32) -> Self {
+        self.style.rotate = Some(value);
+
+        self
+    }
+
+    #[must_use]
+    pub fn scale<T: Into<Vector>>(mut self, value: T) -> Self {
+        self.style.scale = Some(value.into());
+
+        self
+    }
+
+    /// Skews by angles in degrees.
+    #[must_use]
+    pub fn skew<T: Into<Vector>>(mut self, value: T) -> Self {
+        self.style.skew = Some(value.into());
+
+        self
+    }
+
+    #[must_use]
+    pub fn transform_origin<T: Into<TransformOrigin>>(mut self, value: T) -> Self {
+        self.style.transform_origin = Some(value.into());
+
+        self
+    }
+
+    #[must_use]
+    pub fn id<T: Into<String>>(mut self, value: T) -> Self {
+        self.style.id = Some(value.into());
+
+        self
+    }
+
+    #[must_use]
+    pub fn key<T: Into<String>>(mut self, value: T) -> Self {
+        self.style.key = Some(value.into());
+
+        self
+    }
+
+    /// Adds whitespace separated classes, e.g. `"card primary"`.
+    #[must_use]
+    pub fn class<T: AsRef<str>>(mut self, value: T) -> Self {
+        self.style
+            .classes
+            .extend(value.as_ref().split_whitespace().map(ToString::to_string));
+
+        self
+    }
+
+    #[must_use]
+    pub fn state<T: Into<String>>(mut self, value: T) -> Self {
+        self.style.states.push(value.into());
+
+        self
+    }
+
     #[must_use]
     pub fn build(self) -> Node {
         Node {
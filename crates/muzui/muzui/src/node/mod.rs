@@ -1,11 +1,19 @@
 use crate::{
-    graphics::Context, layout::{Layout, MeasureNode, Measurer, Rect}, styling::Style, ContainerElement, Element, GridElement, ImageElement, Masonry, TextElement
+    graphics::LayoutContext, layout::{Constraints, Layout, LengthContext, MeasureNode, Measurer, Point, Rect, Size}, styling::{Style, Stylesheet}, ContainerElement, Element, GridElement, GridError, ImageElement, Masonry, TextElement
+};
+use crate::lang::{
+    node::{parse_layout, Argument, SourceNode},
+    parser::ParseError,
+    style::Theme,
 };
 pub use builder::NodeBuilder;
-use skia_safe::{Canvas, Paint, RRect, Rect as SkRect};
-use std::fmt::Display;
+pub use tree::{LayoutEntry, LayoutIter, LayoutTree};
+use skia_safe::{Canvas, CubicResampler, Matrix, Paint, RRect, Rect as SkRect};
+use serde_json::Value;
+use std::{fmt::Display, fs};
 
 mod builder;
+mod tree;
 
 #[derive(Debug, Clone)]
 pub struct Node {
@@ -38,6 +46,13 @@ impl Node {
         NodeBuilder::new(Element::Text(TextElement::new(data.into())))
     }
 
+    /// A run of text styled on its own, meant to be a child of a text node.
+    ///
+    /// Spans are text nodes of the kind `Span`, so they can hold spans and inline boxes as well.
+    pub fn span(data: impl Into<String>) -> NodeBuilder {
+        NodeBuilder::new(Element::Text(TextElement::span(data.into())))
+    }
+
     #[must_use]
     pub fn image<T: Display>(name: T, data: Vec<u8>) -> NodeBuilder {
         NodeBuilder::new(Element::Image(ImageElement::new(name, data)))
@@ -50,7 +65,208 @@ impl Node {
         NodeBuilder { style, element }
     }
 
-    pub(crate) fn draw(&self, canvas: &Canvas, context: &Context, node: MeasureNode) {
+    /// Parses a layout of the layout language, filling it from `data` and applying its `style` blocks.
+    ///
+    /// `Text` and `Span` take their text, `Image("path")` reads the image from `path`
+    /// and `Masonry(width)` takes the item width.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the layout failed to parse, names an unknown element or an image can't be read
+    pub fn parse(source: &str, theme: &Theme, data: &Value) -> Result<Self, ParseError> {
+        let (stylesheet, source) = parse_layout(source, theme, data)?;
+        let mut node = Self::from_source(source)?;
+
+        node.apply_stylesheet(&stylesheet);
+
+        Ok(node)
+    }
+
+    fn from_source(source: SourceNode) -> Result<Self, ParseError> {
+        let SourceNode {
+            element,
+            args,
+            style,
+            spacing,
+            grid,
+            children,
+        } = source;
+
+        let text = args.first().map(|arg| match arg {
+            Argument::Text(value) => value.clone(),
+            Argument::Number(value) => value.to_string(),
+        });
+
+        let mut builder = match element.as_str() {
+            "Column" => Self::column(),
+            "Row" => Self::row(),
+            "Grid" => Self::grid(),
+            "Masonry" => match args.first() {
+                Some(Argument::Number(width)) => Self::masonry(*width),
+                _ => return Err(ParseError::new("Masonry expects an item width")),
+            },
+            "Text" => Self::text(text.unwrap_or_default()),
+            "Span" => Self::span(text.unwrap_or_default()),
+            "Image" => {
+                let path = text.ok_or_else(|| ParseError::new("Image expects a path"))?;
+                let data = fs::read(&path).map_err(|error| {
+                    ParseError::new(format!("failed to read image {path}: {error}"))
+                })?;
+
+                Self::image(path, data)
+            }
+            name => return Err(ParseError::new(format!("Unknown element {name}"))),
+        };
+
+        builder.style = style;
+
+        if let Some(spacing) = spacing {
+            builder = builder.spacing(spacing);
+        }
+
+        Ok(builder
+            .grid_attributes(grid)
+            .children(
+                children
+                    .into_iter()
+                    .map(Self::from_source)
+                    .collect::<Result<_, _>>()?,
+            )
+            .build())
+    }
+
+    /// The element name, as used by stylesheets, e.g. `Column`.
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        self.element.kind()
+    }
+
+    #[must_use]
+    pub fn id(&self) -> Option<&str> {
+        self.style.id.as_deref()
+    }
+
+    #[must_use]
+    pub fn key(&self) -> Option<&str> {
+        self.style.key.as_deref()
+    }
+
+    pub(crate) const fn element(&self) -> &Element {
+        &self.element
+    }
+
+    /// Checks this node and all of its descendants for configurations that can't be laid out as specified.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found.
+    pub fn validate(&self) -> Result<(), GridError> {
+        if let Element::Grid(grid) = &self.element {
+            grid.validate()?;
+        }
+
+        self.element.children().iter().try_for_each(Self::validate)
+    }
+
+    /// Resolves the style of this node and all of its descendants against `stylesheet`.
+    ///
+    /// Besides the states set on the style, nodes match `first-child`, `last-child` and
+    /// `only-child` by their place among siblings.
+    pub fn apply_stylesheet(&mut self, stylesheet: &Stylesheet) {
+        self.apply_stylesheet_with(stylesheet, &[]);
+    }
+
+    fn apply_stylesheet_with(&mut self, stylesheet: &Stylesheet, states: &[&str]) {
+        self.style = stylesheet.compute(self.element.kind(), &self.style, states);
+
+        let children = self.element.children_mut();
+        let last = children.len().saturating_sub(1);
+
+        for (index, child) in children.iter_mut().enumerate() {
+            let states = match (index == 0, index == last) {
+                (true, true) => &["first-child", "last-child", "only-child"][..],
+                (true, false) => &["first-child"][..],
+                (false, true) => &["last-child"][..],
+                (false, false) => &[][..],
+            };
+
+            child.apply_stylesheet_with(stylesheet, states);
+        }
+    }
+
+    /// Passes inherited properties (color and typography) from every node down to its children.
+    pub fn inherit_styles(&mut self) {
+        let Self { style, element } = self;
+
+        for child in element.children_mut() {
+            child.style.inherit(style);
+            child.inherit_styles();
+        }
+    }
+
+    /// Lays out this node and its descendants in `size` without drawing them, the root at the origin.
+    ///
+    /// Inherited styles are resolved on a copy, stylesheets aren't applied (see [`Node::apply_stylesheet`]).
+    /// An infinite axis lets the root take the size of its content, e.g. to pick the surface size.
+    #[must_use]
+    pub fn layout(&self, context: &LayoutContext, size: Size) -> MeasureNode {
+        let mut node = self.clone();
+
+        node.inherit_styles();
+        context.set_root(&node.style);
+
+        Measurer::layout(&node, context, Rect::new(Point::default(), size))
+    }
+
+    /// Builds the matrix of the transforms of this node, if it has any.
+    fn transform(&self, context: &LayoutContext, node: &MeasureNode) -> Option<Matrix> {
+        let style = &self.style;
+
+        if style.translate.is_none()
+            && style.rotate.is_none()
+            && style.scale.is_none()
+            && style.skew.is_none()
+        {
+            return None;
+        }
+
+        let lengths = LengthContext::new(context, style, Constraints::tight(node.outer.size))
+            .with_size(node.outer.size);
+
+        let origin = style.transform_origin.clone().unwrap_or_default();
+        let origin = (
+            node.outer.origin.x
+                + lengths
+                    .resolve_width(&origin.x)
+                    .unwrap_or(node.outer.size.width / 2.0),
+            node.outer.origin.y
+                + lengths
+                    .resolve_height(&origin.y)
+                    .unwrap_or(node.outer.size.height / 2.0),
+        );
+
+        let translate = style.translate.unwrap_or_default();
+
+        let mut matrix = Matrix::translate((origin.0 + translate.x, origin.1 + translate.y));
+
+        if let Some(rotate) = style.rotate {
+            matrix.pre_rotate(rotate, None);
+        }
+
+        if let Some(scale) = style.scale {
+            matrix.pre_scale((scale.x, scale.y), None);
+        }
+
+        if let Some(skew) = style.skew {
+            matrix.pre_skew((skew.x.to_radians().tan(), skew.y.to_radians().tan()), None);
+        }
+
+        matrix.pre_translate((-origin.0, -origin.1));
+
+        Some(matrix)
+    }
+
+    pub(crate) fn draw(&self, canvas: &Canvas, context: &LayoutContext, node: MeasureNode) {
         let rect = SkRect::from_xywh(
             node.outer.origin.x,
             node.outer.origin.y,
@@ -58,26 +274,25 @@ impl Node {
             node.outer.size.height,
         );
 
+        let corner_radius = self.style.corner_radius.unwrap_or_default();
+
         let round_rect = RRect::new_rect_radii(
             rect,
             &[
-                (self.style.corner_radius.left, self.style.corner_radius.left).into(),
-                (self.style.corner_radius.top, self.style.corner_radius.top).into(),
-                (
-                    self.style.corner_radius.right,
-                    self.style.corner_radius.right,
-                )
-                    .into(),
-                (
-                    self.style.corner_radius.bottom,
-                    self.style.corner_radius.bottom,
-                )
-                    .into(),
+                (corner_radius.left, corner_radius.left).into(),
+                (corner_radius.top, corner_radius.top).into(),
+                (corner_radius.right, corner_radius.right).into(),
+                (corner_radius.bottom, corner_radius.bottom).into(),
             ],
         );
 
         canvas.save();
 
+        // Children are drawn before restoring, so they're transformed along with their parent.
+        if let Some(matrix) = self.transform(context, &node) {
+            canvas.concat(&matrix);
+        }
+
         canvas.clip_rrect(round_rect, None, Some(true));
 
         let mut background = Paint::default();
@@ -105,57 +320,30 @@ impl Node {
                 }
             }
             Element::Image(image) => {
-                canvas.draw_image_rect(&image.data, None, rect, &background);
-            }
-            Element::Text(text) => {
-                let mut paragraph = context.create_paragraph(&self.style, &text.data);
-
-                paragraph.layout(if node.inner.size.width == 0.0 {
-                    context.bounds.width()
-                } else {
-                    node.inner.size.width
-                });
-
-                paragraph.paint(canvas, (node.inner.origin.x, node.inner.origin.y));
+                // Cubic sampling keeps images sharp when the canvas is scaled for a high density output.
+                canvas.draw_image_rect_with_sampling_options(
+                    &image.data,
+                    None,
+                    rect,
+                    CubicResampler::mitchell(),
+                    &background,
+                );
             }
+            Element::Text(text) => text.draw(canvas, context, &self.style, node),
         }
 
         canvas.restore();
-
-        // DEBUG INFO
-
-        // let mut paint = Paint::default();
-
-        // paint.set_color(Argb::new(255, 255, 255, 0).as_color());
-
-        // let font = context.get_font(&self.style);
-        // let info = format!(
-        //     "{}x{} at {}x{}",
-        //     node.size.width, node.size.height, node.position.x, node.position.y
-        // );
-
-        // let bounds = font.measure_str(&info, None).1;
-
-        // canvas.draw_str(
-        //     &info,
-        //     (
-        //         node.position.x + (node.size.width / 2.0) - (bounds.width() / 2.0),
-        //         node.position.y + (node.size.height / 2.0),
-        //     ),
-        //     &font,
-        //     &paint,
-        // );
     }
 }
 
-impl Measurer<Context> for Node {
-    fn measure(&self, context: &Context, parent: &Rect) -> MeasureNode {
+impl Measurer<LayoutContext> for Node {
+    fn measure(&self, context: &LayoutContext, constraints: Constraints) -> MeasureNode {
         match &self.element {
-            Element::Container(container) => container.measure(context, &self.style, parent),
-            Element::Masonry(masonry) => masonry.measure(context, &self.style, parent),
-            Element::Grid(grid) => grid.measure(context, &self.style, parent),
-            Element::Image(image) => image.measure(context, &self.style, parent),
-            Element::Text(text) => text.measure(context, &self.style, parent),
+            Element::Container(container) => container.measure(context, &self.style, constraints),
+            Element::Masonry(masonry) => masonry.measure(context, &self.style, constraints),
+            Element::Grid(grid) => grid.measure(context, &self.style, constraints),
+            Element::Image(image) => image.measure(context, &self.style, constraints),
+            Element::Text(text) => text.measure(context, &self.style, constraints),
         }
     }
 
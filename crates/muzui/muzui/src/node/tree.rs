@@ -0,0 +1,212 @@
+use serde::Serialize;
+
+use crate::{
+    layout::{MeasureNode, Rect},
+    styling::Style,
+    Element, Node,
+};
+
+/// A node tree along with its layout, as rendered.
+///
+/// The node has its stylesheet applied and the layout is in absolute coordinates.
+#[derive(Debug, Clone)]
+pub struct LayoutTree {
+    node: Node,
+    layout: MeasureNode,
+}
+
+impl LayoutTree {
+    /// Pairs `node` with its layout, `layout` has to be a layout of `node`.
+    #[must_use]
+    pub const fn new(node: Node, layout: MeasureNode) -> Self {
+        Self { node, layout }
+    }
+
+    #[must_use]
+    pub const fn node(&self) -> &Node {
+        &self.node
+    }
+
+    #[must_use]
+    pub const fn layout(&self) -> &MeasureNode {
+        &self.layout
+    }
+
+    #[must_use]
+    pub fn into_parts(self) -> (Node, MeasureNode) {
+        (self.node, self.layout)
+    }
+
+    /// Iterates over every node with its layout, parents before their children.
+    #[must_use]
+    pub fn iter(&self) -> LayoutIter<'_> {
+        LayoutIter {
+            stack: vec![(&self.node, &self.layout)],
+        }
+    }
+
+    /// Finds the first node matching `predicate`, parents before their children.
+    pub fn find<P: FnMut(&Node) -> bool>(&self, mut predicate: P) -> Option<(&Node, &MeasureNode)> {
+        self.iter().find(|(node, _)| predicate(node))
+    }
+
+    #[must_use]
+    pub fn find_by_id(&self, id: &str) -> Option<(&Node, &MeasureNode)> {
+        self.find(|node| node.id() == Some(id))
+    }
+
+    #[must_use]
+    pub fn find_by_key(&self, key: &str) -> Option<(&Node, &MeasureNode)> {
+        self.find(|node| node.key() == Some(key))
+    }
+
+    /// Describes every node with its rects and resolved style, to be serialized.
+    #[must_use]
+    pub fn export(&self) -> LayoutEntry<'_> {
+        LayoutEntry::new(&self.node, &self.layout)
+    }
+
+    /// Serializes [`LayoutTree::export`] as pretty printed JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        // The entries hold no maps and non-finite numbers become `null`, so serializing can't fail.
+        serde_json::to_string_pretty(&self.export()).unwrap_or_default()
+    }
+}
+
+/// A node of an exported [`LayoutTree`].
+#[derive(Debug, Clone, Serialize)]
+pub struct LayoutEntry<'a> {
+    /// The element name, as used by stylesheets, e.g. `Column`.
+    pub kind: &'static str,
+    pub id: Option<&'a str>,
+    pub key: Option<&'a str>,
+    /// The text of text nodes and spans, without their children.
+    pub text: Option<&'a str>,
+    pub outer: Rect,
+    pub inner: Rect,
+    pub style: &'a Style,
+    pub children: Vec<Self>,
+}
+
+impl<'a> LayoutEntry<'a> {
+    fn new(node: &'a Node, layout: &MeasureNode) -> Self {
+        Self {
+            kind: node.kind(),
+            id: node.id(),
+            key: node.key(),
+            text: match node.element() {
+                Element::Text(text) => Some(text.data.as_str()),
+                _ => None,
+            },
+            outer: layout.outer,
+            inner: layout.inner,
+            style: &node.style,
+            children: node
+                .element()
+                .children()
+                .iter()
+                .zip(&layout.children)
+                .map(|(child, layout)| Self::new(child, layout))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a LayoutTree {
+    type Item = (&'a Node, &'a MeasureNode);
+    type IntoIter = LayoutIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the nodes of a [`LayoutTree`] with their layouts.
+pub struct LayoutIter<'a> {
+    stack: Vec<(&'a Node, &'a MeasureNode)>,
+}
+
+impl<'a> Iterator for LayoutIter<'a> {
+    type Item = (&'a Node, &'a MeasureNode);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, layout) = self.stack.pop()?;
+
+        self.stack
+            .extend(node.element().children().iter().zip(&layout.children).rev());
+
+        Some((node, layout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LayoutTree;
+    use crate::{
+        layout::{MeasureNode, Point, Rect, Size},
+        Node,
+    };
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> MeasureNode {
+        let rect = Rect::new(Point::new(x, y), Size::new(width, height));
+
+        MeasureNode {
+            outer: rect,
+            inner: rect,
+            children: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        let node = Node::column()
+            .child(
+                Node::row()
+                    .id("header")
+                    .child(Node::text("Title").key("title").build())
+                    .build(),
+            )
+            .child(Node::text("Body").key("body").build())
+            .build();
+
+        let mut layout = rect(0.0, 0.0, 100.0, 100.0);
+        let mut header = rect(0.0, 0.0, 100.0, 20.0);
+
+        header.children.push(rect(0.0, 0.0, 40.0, 20.0));
+        layout
+            .children
+            .extend([header, rect(0.0, 20.0, 100.0, 80.0)]);
+
+        let tree = LayoutTree::new(node, layout);
+
+        assert_eq!(
+            tree.iter()
+                .map(|(node, layout)| (node.key(), layout.outer.size.height))
+                .collect::<Vec<_>>(),
+            [
+                (None, 100.0),
+                (None, 20.0),
+                (Some("title"), 20.0),
+                (Some("body"), 80.0)
+            ]
+        );
+
+        assert_eq!(
+            tree.find_by_id("header")
+                .map(|(_, layout)| layout.outer.size.width),
+            Some(100.0)
+        );
+        assert_eq!(
+            tree.find_by_key("body")
+                .map(|(_, layout)| layout.outer.origin.y),
+            Some(20.0)
+        );
+        assert!(tree.find_by_id("footer").is_none());
+
+        let json = tree.to_json();
+
+        assert!(json.contains(r#""kind": "Row""#));
+        assert!(json.contains(r#""text": "Body""#));
+    }
+}
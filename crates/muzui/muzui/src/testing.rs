@@ -0,0 +1,627 @@
+//! Golden image tests: renders nodes and compares them to reference images.
+//!
+//! References are only recorded when [`UPDATE_VAR`] is set or [`Snapshots::update`] asks for it,
+//! a missing reference fails the test.
+//! When a render differs or has no reference, `<name>.new.png` is written next to the reference,
+//! along with `<name>.diff.png` for renders that differ.
+//!
+//! Layouts can also be checked without rendering: [`layout`] measures a node and the `assert_*`
+//! functions check the border boxes of nodes found by id.
+
+use std::{
+    env,
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use skia_safe::{
+    images, AlphaType, CachingHint, ColorType, Data, EncodedImageFormat, Image, ImageInfo,
+};
+
+use crate::{
+    layout::{MeasureNode, Rect, Size},
+    LayoutContext, LayoutTree, Node, RenderContext,
+};
+
+/// Fonts bundled for tests, so text renders the same on every machine.
+pub const FONTS: [&[u8]; 3] = [
+    include_bytes!("../fonts/DejaVuSans.ttf"),
+    include_bytes!("../fonts/DejaVuSans-Bold.ttf"),
+    include_bytes!("../fonts/DejaVuSansMono.ttf"),
+];
+
+/// The family of the bundled sans-serif font, regular and bold.
+pub const SANS: &str = "DejaVu Sans";
+/// The family of the bundled monospace font.
+pub const MONO: &str = "DejaVu Sans Mono";
+
+/// Set to anything but `0` to record renders as the new references instead of comparing them.
+pub const UPDATE_VAR: &str = "MUZUI_UPDATE_SNAPSHOTS";
+
+/// Creates a layout context with the bundled fonts registered and no installed fonts,
+/// families that aren't bundled use [`SANS`].
+#[must_use]
+pub fn layout_context(width: f32, height: f32) -> LayoutContext {
+    let mut context = LayoutContext::without_system_fonts(width, height, SANS);
+
+    for font in FONTS {
+        // The bundled fonts are valid, so registering them can't fail.
+        let _ = context.register_font(font, None);
+    }
+
+    context
+}
+
+/// Creates a render context with the bundled fonts registered.
+#[must_use]
+pub fn render_context(width: i16, height: i16) -> Option<RenderContext> {
+    RenderContext::from_layout_context(layout_context(f32::from(width), f32::from(height)))
+}
+
+/// Lays out `node` in a `width` × `height` viewport with the bundled fonts, without drawing it.
+///
+/// Stylesheets aren't applied, see [`Node::apply_stylesheet`].
+#[must_use]
+pub fn layout(node: &Node, width: f32, height: f32) -> LayoutTree {
+    LayoutTree::new(
+        node.clone(),
+        node.layout(&layout_context(width, height), Size::new(width, height)),
+    )
+}
+
+/// How far layout values may be off, to allow for rounding.
+const EPSILON: f32 = 0.01;
+
+fn near(a: f32, b: f32) -> bool {
+    (a - b).abs() <= EPSILON
+}
+
+#[track_caller]
+fn find<'a>(tree: &'a LayoutTree, id: &str) -> &'a MeasureNode {
+    match tree.find_by_id(id) {
+        Some((_, layout)) => layout,
+        None => panic!("no node with id `{id}`"),
+    }
+}
+
+/// Returns the border box of the node with the given id.
+///
+/// # Panics
+///
+/// Panics if no node has the id
+#[must_use]
+#[track_caller]
+pub fn outer(tree: &LayoutTree, id: &str) -> Rect {
+    find(tree, id).outer
+}
+
+/// Returns the content box of the node with the given id.
+///
+/// # Panics
+///
+/// Panics if no node has the id
+#[must_use]
+#[track_caller]
+pub fn inner(tree: &LayoutTree, id: &str) -> Rect {
+    find(tree, id).inner
+}
+
+/// Asserts that the border box of the node with the given id is `expected`, give or take rounding.
+///
+/// # Panics
+///
+/// Panics if the rects differ or no node has the id
+#[track_caller]
+pub fn assert_rect(tree: &LayoutTree, id: &str, expected: Rect) {
+    let actual = outer(tree, id);
+    let edges = |rect: Rect| [rect.x(), rect.y(), rect.width(), rect.height()];
+
+    assert!(
+        edges(actual)
+            .into_iter()
+            .zip(edges(expected))
+            .all(|(actual, expected)| near(actual, expected)),
+        "`{id}` is at {actual:?}, expected {expected:?}"
+    );
+}
+
+/// Asserts that the border box of `child` lies inside the one of `parent`.
+///
+/// # Panics
+///
+/// Panics if `child` sticks out or either id is missing
+#[track_caller]
+pub fn assert_contains(tree: &LayoutTree, parent: &str, child: &str) {
+    let (outer_rect, inner_rect) = (outer(tree, parent), outer(tree, child));
+    let bounds = Rect::from_xywh(
+        outer_rect.x() - EPSILON,
+        outer_rect.y() - EPSILON,
+        2.0f32.mul_add(EPSILON, outer_rect.width()),
+        2.0f32.mul_add(EPSILON, outer_rect.height()),
+    );
+
+    assert!(
+        bounds.contains(&inner_rect),
+        "`{child}` at {inner_rect:?} isn't inside `{parent}` at {outer_rect:?}"
+    );
+}
+
+/// Asserts that the border boxes of `a` and `b` share some area.
+///
+/// # Panics
+///
+/// Panics if the nodes don't overlap or either id is missing
+#[track_caller]
+pub fn assert_overlap(tree: &LayoutTree, a: &str, b: &str) {
+    let (a_rect, b_rect) = (outer(tree, a), outer(tree, b));
+
+    assert!(
+        a_rect.intersects(&b_rect),
+        "`{a}` at {a_rect:?} doesn't overlap `{b}` at {b_rect:?}"
+    );
+}
+
+/// Asserts that the border boxes of `a` and `b` share no area, touching edges are fine.
+///
+/// # Panics
+///
+/// Panics if the nodes overlap or either id is missing
+#[track_caller]
+pub fn assert_no_overlap(tree: &LayoutTree, a: &str, b: &str) {
+    let (a_rect, b_rect) = (outer(tree, a), outer(tree, b));
+    let shrunk = Rect::from_xywh(
+        a_rect.x() + EPSILON,
+        a_rect.y() + EPSILON,
+        2.0f32.mul_add(-EPSILON, a_rect.width()),
+        2.0f32.mul_add(-EPSILON, a_rect.height()),
+    );
+
+    assert!(
+        !shrunk.intersects(&b_rect),
+        "`{a}` at {a_rect:?} overlaps `{b}` at {b_rect:?}"
+    );
+}
+
+/// Asserts that the border box of `second` starts `spacing` after the one of `first`,
+/// to its right when they share some height and below it when they share some width,
+/// like siblings of a row or a column.
+///
+/// # Panics
+///
+/// Panics if the distance differs, the nodes share neither a row nor a column or either id is missing
+#[track_caller]
+pub fn assert_spacing(tree: &LayoutTree, first: &str, second: &str, spacing: f32) {
+    let (a, b) = (outer(tree, first), outer(tree, second));
+
+    let distance = if a.y() < b.bottom() && b.y() < a.bottom() {
+        b.x() - a.right()
+    } else if a.x() < b.right() && b.x() < a.right() {
+        b.y() - a.bottom()
+    } else {
+        panic!("`{first}` at {a:?} and `{second}` at {b:?} share neither a row nor a column");
+    };
+
+    assert!(
+        near(distance, spacing),
+        "`{second}` is {distance} after `{first}`, expected {spacing}"
+    );
+}
+
+/// A render that doesn't match its reference.
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(io::Error),
+    /// There is no reference yet, it's recorded by running the test with [`UPDATE_VAR`] set.
+    Missing(PathBuf),
+    /// The node couldn't be rendered or the reference couldn't be decoded.
+    Render,
+    /// The render has a different size than the reference.
+    Size {
+        expected: (i32, i32),
+        actual: (i32, i32),
+    },
+    /// More pixels than allowed differ from the reference.
+    Mismatch {
+        pixels: usize,
+        total: usize,
+    },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to access snapshot: {error}"),
+            Self::Missing(path) => write!(
+                f,
+                "no reference at {}, run with {UPDATE_VAR}=1 to record it",
+                path.display()
+            ),
+            Self::Render => f.write_str("failed to render or decode snapshot"),
+            Self::Size { expected, actual } => write!(
+                f,
+                "snapshot is {}x{}, expected {}x{}",
+                actual.0, actual.1, expected.0, expected.1
+            ),
+            Self::Mismatch { pixels, total } => {
+                write!(f, "{pixels} of {total} pixels differ from the snapshot")
+            }
+        }
+    }
+}
+
+impl Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SnapshotError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Compares renders to the reference images of a directory.
+#[derive(Debug, Clone)]
+pub struct Snapshots {
+    dir: PathBuf,
+    threshold: f32,
+    max_mismatch: f32,
+    update: bool,
+}
+
+impl Snapshots {
+    /// Keeps references in `tests/snapshots` of the crate under test.
+    #[must_use]
+    pub fn new() -> Self {
+        let root = env::var_os("CARGO_MANIFEST_DIR").map_or_else(PathBuf::new, PathBuf::from);
+
+        Self::with_dir(root.join("tests").join("snapshots"))
+    }
+
+    #[must_use]
+    pub fn with_dir<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir: dir.into(),
+            threshold: 0.1,
+            max_mismatch: 0.0,
+            update: env::var_os(UPDATE_VAR).is_some_and(|value| value != "0"),
+        }
+    }
+
+    /// Sets the perceptual difference from 0 to 1 under which pixels are considered equal,
+    /// `0.1` by default.
+    #[must_use]
+    pub const fn threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+
+        self
+    }
+
+    /// Sets the share of pixels from 0 to 1 allowed to differ, none by default.
+    #[must_use]
+    pub const fn max_mismatch(mut self, max_mismatch: f32) -> Self {
+        self.max_mismatch = max_mismatch;
+
+        self
+    }
+
+    /// Sets whether renders are recorded as the new references instead of compared to them,
+    /// by default whether [`UPDATE_VAR`] is set.
+    #[must_use]
+    pub const fn update(mut self, update: bool) -> Self {
+        self.update = update;
+
+        self
+    }
+
+    /// Renders `node` with `context` and compares it to the reference called `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the render doesn't match the reference or the files can't be accessed
+    pub fn check(
+        &self,
+        name: &str,
+        context: &mut RenderContext,
+        node: &Node,
+    ) -> Result<(), SnapshotError> {
+        context.render(node);
+
+        let data = context.encode().ok_or(SnapshotError::Render)?;
+
+        let path = self.dir.join(format!("{name}.png"));
+        let new_path = self.dir.join(format!("{name}.new.png"));
+        let diff_path = self.dir.join(format!("{name}.diff.png"));
+
+        if self.update {
+            fs::create_dir_all(&self.dir)?;
+            fs::write(&path, data)?;
+
+            return remove_stale(&[&new_path, &diff_path]);
+        }
+
+        if !path.exists() {
+            fs::create_dir_all(&self.dir)?;
+            fs::write(&new_path, data)?;
+
+            return Err(SnapshotError::Missing(path));
+        }
+
+        let expected = Pixels::decode(&fs::read(&path)?).ok_or(SnapshotError::Render)?;
+        let actual = Pixels::decode(&data).ok_or(SnapshotError::Render)?;
+
+        if (expected.width, expected.height) != (actual.width, actual.height) {
+            fs::write(&new_path, data)?;
+
+            return Err(SnapshotError::Size {
+                expected: (expected.width, expected.height),
+                actual: (actual.width, actual.height),
+            });
+        }
+
+        let (pixels, diff) = compare(&expected.data, &actual.data, self.threshold);
+        let total = expected.data.len() / 4;
+
+        if pixels as f32 > total as f32 * self.max_mismatch {
+            let diff = Pixels {
+                data: diff,
+                ..expected
+            }
+            .encode()
+            .ok_or(SnapshotError::Render)?;
+
+            fs::write(&new_path, data)?;
+            fs::write(&diff_path, diff)?;
+
+            return Err(SnapshotError::Mismatch { pixels, total });
+        }
+
+        remove_stale(&[&new_path, &diff_path])
+    }
+
+    /// Renders `node` with `context` and compares it to the reference called `name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the render doesn't match the reference
+    #[track_caller]
+    pub fn assert(&self, name: &str, context: &mut RenderContext, node: &Node) {
+        if let Err(error) = self.check(name, context, node) {
+            panic!("snapshot {name}: {error}");
+        }
+    }
+}
+
+impl Default for Snapshots {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Removes the outputs of an earlier failure.
+fn remove_stale(paths: &[&Path]) -> Result<(), SnapshotError> {
+    for path in paths {
+        match fs::remove_file(path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error.into()),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Unpremultiplied RGBA pixels.
+struct Pixels {
+    width: i32,
+    height: i32,
+    data: Vec<u8>,
+}
+
+impl Pixels {
+    fn info(width: i32, height: i32) -> ImageInfo {
+        ImageInfo::new(
+            (width, height),
+            ColorType::RGBA8888,
+            AlphaType::Unpremul,
+            None,
+        )
+    }
+
+    fn decode(data: &[u8]) -> Option<Self> {
+        let image = Image::from_encoded(Data::new_copy(data))?;
+        let info = Self::info(image.width(), image.height());
+
+        let mut pixels = vec![0; info.compute_min_byte_size()];
+
+        image
+            .read_pixels(
+                &info,
+                &mut pixels,
+                info.min_row_bytes(),
+                (0, 0),
+                CachingHint::Disallow,
+            )
+            .then_some(Self {
+                width: image.width(),
+                height: image.height(),
+                data: pixels,
+            })
+    }
+
+    fn encode(&self) -> Option<Vec<u8>> {
+        let info = Self::info(self.width, self.height);
+
+        images::raster_from_data(&info, Data::new_copy(&self.data), info.min_row_bytes())?
+            .encode(None, EncodedImageFormat::PNG, 100)
+            .map(|data| data.to_vec())
+    }
+}
+
+/// The largest possible [`color_delta`].
+const MAX_DELTA: f32 = 35215.0;
+
+/// Blends an RGBA pixel onto white.
+fn blend(pixel: &[u8]) -> [f32; 3] {
+    let alpha = f32::from(pixel[3]) / 255.0;
+
+    [pixel[0], pixel[1], pixel[2]].map(|channel| (f32::from(channel) - 255.0).mul_add(alpha, 255.0))
+}
+
+/// Converts a color to YIQ, whose distances follow perceived differences better than RGB ones.
+fn yiq([red, green, blue]: [f32; 3]) -> [f32; 3] {
+    [
+        red.mul_add(0.298_895_3, green.mul_add(0.586_622_5, blue * 0.114_482_2)),
+        red.mul_add(0.595_978, green.mul_add(-0.274_176_1, blue * -0.321_801_9)),
+        red.mul_add(0.211_470_2, green.mul_add(-0.522_617_1, blue * 0.311_146_9)),
+    ]
+}
+
+/// The squared perceptual distance between two RGBA pixels.
+fn color_delta(a: &[u8], b: &[u8]) -> f32 {
+    let (a, b) = (yiq(blend(a)), yiq(blend(b)));
+    let [luma, in_phase, quadrature] = [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+
+    (0.5053 * luma).mul_add(
+        luma,
+        (0.299 * in_phase).mul_add(in_phase, 0.1957 * quadrature * quadrature),
+    )
+}
+
+/// Compares two RGBA images of the same size, returning the number of pixels whose
+/// perceptual difference exceeds `threshold`, from 0 to 1, and an image showing them
+/// in red over the faded reference.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn compare(expected: &[u8], actual: &[u8], threshold: f32) -> (usize, Vec<u8>) {
+    let max = MAX_DELTA * threshold * threshold;
+
+    let mut mismatched = 0;
+    let mut diff = Vec::with_capacity(expected.len());
+
+    for (expected, actual) in expected.chunks_exact(4).zip(actual.chunks_exact(4)) {
+        if color_delta(expected, actual) > max {
+            mismatched += 1;
+
+            diff.extend([255, 0, 0, 255]);
+        } else {
+            let [luma, ..] = yiq(blend(expected));
+            let gray = (luma - 255.0).mul_add(0.1, 255.0) as u8;
+
+            diff.extend([gray, gray, gray, 255]);
+        }
+    }
+
+    (mismatched, diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{
+        assert_contains, assert_no_overlap, assert_overlap, assert_rect, assert_spacing, compare,
+        layout, render_context, SnapshotError, Snapshots,
+    };
+    use crate::{layout::Rect, styling::Length, GridLength, Node};
+
+    fn block(id: &str, width: f32, height: f32) -> Node {
+        Node::column()
+            .id(id)
+            .width(Length::Px(width))
+            .height(Length::Px(height))
+            .build()
+    }
+
+    #[test]
+    fn test_compare() {
+        let white = [255, 255, 255, 255];
+        let black = [0, 0, 0, 255];
+
+        let expected = [white, black, [200, 0, 0, 255]].concat();
+        let actual = [[0, 0, 0, 0], black, [202, 1, 0, 255]].concat();
+
+        let (mismatched, diff) = compare(&expected, &actual, 0.1);
+
+        assert_eq!(mismatched, 0);
+        assert_eq!(diff[4..8], [229, 229, 229, 255]);
+
+        let (mismatched, diff) = compare(&expected, &[black, black, black].concat(), 0.1);
+
+        assert_eq!(mismatched, 2);
+        assert_eq!(diff[..4], [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_snapshots() {
+        let dir = env::temp_dir().join(format!("muzui-snapshots-{}", process::id()));
+        let snapshots = Snapshots::with_dir(dir.clone()).update(false);
+        let mut context = render_context(40, 20).expect("failed to create render context");
+
+        let blue = block("box", 40.0, 20.0)
+            .into_builder()
+            .background(0xFF00_00FF)
+            .build();
+        let red = blue.clone().into_builder().background(0xFFFF_0000).build();
+
+        assert!(matches!(
+            snapshots.check("box", &mut context, &blue),
+            Err(SnapshotError::Missing(_))
+        ));
+        assert!(dir.join("box.new.png").exists());
+
+        snapshots
+            .clone()
+            .update(true)
+            .check("box", &mut context, &blue)
+            .expect("failed to record snapshot");
+
+        assert!(!dir.join("box.new.png").exists());
+
+        snapshots.assert("box", &mut context, &blue);
+
+        assert!(matches!(
+            snapshots.check("box", &mut context, &red),
+            Err(SnapshotError::Mismatch { pixels, .. }) if pixels > 0
+        ));
+        assert!(dir.join("box.diff.png").exists());
+
+        fs::remove_dir_all(&dir).expect("failed to remove snapshots");
+    }
+
+    #[test]
+    fn test_layout() {
+        let node = Node::grid()
+            .id("grid")
+            .width(Length::Px(200.0))
+            .padding(5.0)
+            .spacing(10.0)
+            .columns([GridLength::Auto, GridLength::Auto, GridLength::Weight(1.0)])
+            .children(vec![
+                block("a", 40.0, 20.0),
+                block("b", 30.0, 50.0),
+                block("c", 60.0, 10.0),
+                block("d", 40.0, 20.0),
+            ])
+            .build();
+
+        let tree = layout(&node, 400.0, 400.0);
+
+        // The auto columns take the width of their content, the weighted one takes the rest.
+        assert_rect(&tree, "grid", Rect::from_xywh(0.0, 0.0, 200.0, 90.0));
+        assert_rect(&tree, "a", Rect::from_xywh(5.0, 5.0, 40.0, 20.0));
+        assert_rect(&tree, "c", Rect::from_xywh(95.0, 5.0, 60.0, 10.0));
+        assert_rect(&tree, "d", Rect::from_xywh(5.0, 65.0, 40.0, 20.0));
+
+        assert_spacing(&tree, "a", "b", 10.0);
+        assert_spacing(&tree, "b", "c", 10.0);
+        assert_spacing(&tree, "a", "d", 40.0);
+
+        assert_contains(&tree, "grid", "c");
+        assert_overlap(&tree, "grid", "d");
+        assert_no_overlap(&tree, "a", "b");
+    }
+}
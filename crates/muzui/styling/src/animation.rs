@@ -0,0 +1,188 @@
+use crate::{Color, Length, Vector};
+
+/// How the progress between two keyframes is distributed over time.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    /// Maps the linear progress `time` from 0 to 1 to the eased one.
+    #[must_use]
+    pub fn apply(self, time: f32) -> f32 {
+        let time = time.clamp(0.0, 1.0);
+
+        match self {
+            Self::Linear => time,
+            Self::EaseIn => time * time,
+            Self::EaseOut => time * (2.0 - time),
+            Self::EaseInOut => {
+                if time < 0.5 {
+                    2.0 * time * time
+                } else {
+                    2.0f32.mul_add(-time, 4.0).mul_add(time, -1.0)
+                }
+            }
+        }
+    }
+}
+
+/// A value that can be blended towards another one.
+pub trait Interpolate {
+    /// Returns the value at `progress` from 0 (`self`) to 1 (`other`).
+    #[must_use]
+    fn interpolate(&self, other: &Self, progress: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, other: &Self, progress: f32) -> Self {
+        (other - self).mul_add(progress, *self)
+    }
+}
+
+impl Interpolate for Vector {
+    fn interpolate(&self, other: &Self, progress: f32) -> Self {
+        Self::new(
+            self.x.interpolate(&other.x, progress),
+            self.y.interpolate(&other.y, progress),
+        )
+    }
+}
+
+impl Interpolate for Color {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn interpolate(&self, other: &Self, progress: f32) -> Self {
+        let channel = |from: u8, to: u8| {
+            f32::from(from)
+                .interpolate(&f32::from(to), progress)
+                .round()
+                .clamp(0.0, 255.0) as u8
+        };
+
+        Self::from_rgba(
+            channel(self.red, other.red),
+            channel(self.green, other.green),
+            channel(self.blue, other.blue),
+            channel(self.alpha, other.alpha),
+        )
+    }
+}
+
+/// Lengths of the same unit are blended, others switch halfway.
+impl Interpolate for Length {
+    fn interpolate(&self, other: &Self, progress: f32) -> Self {
+        match (self, other) {
+            (Self::Px(from), Self::Px(to)) => Self::Px(from.interpolate(to, progress)),
+            (Self::Percent(from), Self::Percent(to)) => {
+                Self::Percent(from.interpolate(to, progress))
+            }
+            (Self::Vw(from), Self::Vw(to)) => Self::Vw(from.interpolate(to, progress)),
+            (Self::Vh(from), Self::Vh(to)) => Self::Vh(from.interpolate(to, progress)),
+            (Self::Vmin(from), Self::Vmin(to)) => Self::Vmin(from.interpolate(to, progress)),
+            (Self::Vmax(from), Self::Vmax(to)) => Self::Vmax(from.interpolate(to, progress)),
+            (Self::Em(from), Self::Em(to)) => Self::Em(from.interpolate(to, progress)),
+            (Self::Rem(from), Self::Rem(to)) => Self::Rem(from.interpolate(to, progress)),
+            _ if progress < 0.5 => self.clone(),
+            _ => other.clone(),
+        }
+    }
+}
+
+/// Values at points in time from 0 to 1, e.g. the width of a progress bar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyframes<T> {
+    frames: Vec<(f32, T)>,
+    easing: Easing,
+}
+
+impl<T: Interpolate + Clone> Keyframes<T> {
+    /// Creates keyframes starting at `value`.
+    #[must_use]
+    pub fn new(value: T) -> Self {
+        Self {
+            frames: vec![(0.0, value)],
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Adds a keyframe, a keyframe at the same time is replaced.
+    #[must_use]
+    pub fn at(mut self, time: f32, value: T) -> Self {
+        let time = time.clamp(0.0, 1.0);
+        let index = self.frames.partition_point(|(frame, _)| *frame < time);
+
+        match self.frames.get_mut(index) {
+            #[allow(clippy::float_cmp)]
+            Some(frame) if frame.0 == time => frame.1 = value,
+            _ => self.frames.insert(index, (time, value)),
+        }
+
+        self
+    }
+
+    /// Sets the easing used between every pair of keyframes.
+    #[must_use]
+    pub const fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+
+        self
+    }
+
+    /// Returns the value at `time`, the last keyframe holds until the end.
+    #[must_use]
+    pub fn value(&self, time: f32) -> T {
+        let index = self.frames.partition_point(|(frame, _)| *frame <= time);
+
+        match (
+            self.frames.get(index.wrapping_sub(1)),
+            self.frames.get(index),
+        ) {
+            (Some((start, from)), Some((end, to))) => {
+                from.interpolate(to, self.easing.apply((time - start) / (end - start)))
+            }
+            (Some((_, value)), None) | (None, Some((_, value))) => value.clone(),
+            (None, None) => unreachable!("keyframes start with a value"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Easing, Interpolate, Keyframes};
+    use crate::{Color, Length, Vector};
+
+    #[test]
+    fn test_keyframes() {
+        let width = Keyframes::new(Length::Percent(0.0))
+            .at(1.0, Length::Percent(100.0))
+            .at(0.5, Length::Percent(20.0));
+
+        assert_eq!(
+            [0.0, 0.25, 0.75, 2.0].map(|time| width.value(time)),
+            [0.0, 10.0, 60.0, 100.0].map(Length::Percent)
+        );
+
+        let offset = Keyframes::new(Vector::new(0.0, 0.0))
+            .at(1.0, Vector::new(100.0, 0.0))
+            .easing(Easing::EaseIn);
+
+        assert_eq!(offset.value(0.5), Vector::new(25.0, 0.0));
+
+        assert_eq!(
+            Color::from_rgba(0, 0, 0, 0).interpolate(&Color::from_rgb(255, 100, 0), 0.5),
+            Color::from_rgba(128, 50, 0, 128)
+        );
+        assert_eq!(
+            Length::Percent(0.0).interpolate(&Length::Percent(50.0), 0.5),
+            Length::Percent(25.0)
+        );
+        assert_eq!(
+            Length::Px(0.0).interpolate(&Length::Auto, 0.25),
+            Length::Px(0.0)
+        );
+    }
+}
@@ -66,7 +66,10 @@
 //         }
 //     }
 // }
-#[derive(Debug, Clone, Copy)]
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub struct Color {
     pub red: u8,
     pub green: u8,
@@ -1,9 +1,44 @@
-#[derive(Debug, Default, Clone, Copy)]
+use serde::Serialize;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 pub enum FontWeight {
+    Thin,
+    ExtraLight,
     Light,
     #[default]
     Normal,
+    Medium,
+    SemiBold,
     Bold,
+    ExtraBold,
+    Black,
+    /// A weight between 1 and 1000, e.g. 350 for variable fonts.
+    Value(u16),
+}
+
+impl FontWeight {
+    /// The numeric weight, 400 being normal and 700 bold.
+    #[must_use]
+    pub const fn value(self) -> u16 {
+        match self {
+            Self::Thin => 100,
+            Self::ExtraLight => 200,
+            Self::Light => 300,
+            Self::Normal => 400,
+            Self::Medium => 500,
+            Self::SemiBold => 600,
+            Self::Bold => 700,
+            Self::ExtraBold => 800,
+            Self::Black => 900,
+            Self::Value(value) => value,
+        }
+    }
+}
+
+impl From<u16> for FontWeight {
+    fn from(value: u16) -> Self {
+        Self::Value(value)
+    }
 }
 
 // impl Parse for FontWeight {
@@ -19,7 +54,7 @@ pub enum FontWeight {
 //     }
 // }
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 pub enum FontSlant {
     #[default]
     Upright,
@@ -40,7 +75,40 @@ pub enum FontSlant {
 //     }
 // }
 
-#[derive(Debug, Clone, Copy)]
+/// How condensed or expanded the glyphs are, also known as font stretch.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum FontWidth {
+    UltraCondensed,
+    ExtraCondensed,
+    Condensed,
+    SemiCondensed,
+    #[default]
+    Normal,
+    SemiExpanded,
+    Expanded,
+    ExtraExpanded,
+    UltraExpanded,
+}
+
+impl FontWidth {
+    /// The width class, from 1 (ultra condensed) to 9 (ultra expanded).
+    #[must_use]
+    pub const fn value(self) -> u16 {
+        match self {
+            Self::UltraCondensed => 1,
+            Self::ExtraCondensed => 2,
+            Self::Condensed => 3,
+            Self::SemiCondensed => 4,
+            Self::Normal => 5,
+            Self::SemiExpanded => 6,
+            Self::Expanded => 7,
+            Self::ExtraExpanded => 8,
+            Self::UltraExpanded => 9,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct FontSize {
     pub size: f32,
 }
@@ -64,17 +132,41 @@ impl Default for FontSize {
 //     }
 // }
 
-#[derive(Debug, Clone)]
+/// A family along with the ones tried, in order, for characters it doesn't have, e.g. CJK or emoji.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct FontFamily {
     pub family: String,
+    pub fallbacks: Vec<String>,
 }
 
 impl FontFamily {
+    /// Takes a single family or a comma separated list, e.g. `"Inter, Noto Sans JP, Noto Color Emoji"`.
     pub fn new(family: impl Into<String>) -> Self {
+        let family = family.into();
+
+        let mut families = family
+            .split(',')
+            .map(str::trim)
+            .filter(|family| !family.is_empty())
+            .map(ToString::to_string);
+
         Self {
-            family: family.into(),
+            family: families.next().unwrap_or_default(),
+            fallbacks: families.collect(),
         }
     }
+
+    #[must_use]
+    pub fn with_fallback(mut self, family: impl Into<String>) -> Self {
+        self.fallbacks.push(family.into());
+
+        self
+    }
+
+    /// The family followed by its fallbacks.
+    pub fn families(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.family.as_str()).chain(self.fallbacks.iter().map(String::as_str))
+    }
 }
 
 impl Default for FontFamily {
@@ -83,6 +175,26 @@ impl Default for FontFamily {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::FontFamily;
+
+    #[test]
+    fn test_family_list() {
+        let family = FontFamily::new("Inter, Noto Sans JP,,  Noto Color Emoji ");
+
+        assert_eq!(family.family, "Inter");
+        assert_eq!(
+            family.families().collect::<Vec<_>>(),
+            ["Inter", "Noto Sans JP", "Noto Color Emoji"]
+        );
+        assert_eq!(
+            FontFamily::new("Arial").with_fallback("Noto Sans"),
+            FontFamily::new("Arial, Noto Sans")
+        );
+    }
+}
+
 // impl Parse for FontFamily {
 //     fn parse(parser: &mut Parser) -> Result<Self> {
 //         parser
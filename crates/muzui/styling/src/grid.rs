@@ -0,0 +1,27 @@
+use serde::Serialize;
+
+/// How children without an explicit row or column are placed.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize)]
+pub enum GridFlow {
+    /// Fills the grid row by row, adding rows as needed.
+    #[default]
+    Row,
+    /// Fills the grid column by column, adding columns as needed.
+    Column,
+    /// Like [`GridFlow::Row`], but fills holes left by earlier children.
+    RowDense,
+    /// Like [`GridFlow::Column`], but fills holes left by earlier children.
+    ColumnDense,
+}
+
+impl GridFlow {
+    #[must_use]
+    pub const fn is_column(self) -> bool {
+        matches!(self, Self::Column | Self::ColumnDense)
+    }
+
+    #[must_use]
+    pub const fn is_dense(self) -> bool {
+        matches!(self, Self::RowDense | Self::ColumnDense)
+    }
+}
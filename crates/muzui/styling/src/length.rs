@@ -0,0 +1,202 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+use serde::Serialize;
+
+/// A node of a `calc()`-style length expression.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Operation {
+    Add(Length, Length),
+    Sub(Length, Length),
+    Mul(Length, Length),
+    Div(Length, Length),
+    Min(Length, Length),
+    Max(Length, Length),
+    /// Clamps the middle value between the first (minimum) and the last (maximum) one.
+    Clamp(Length, Length, Length),
+}
+
+impl Operation {
+    fn is_auto(&self) -> bool {
+        match self {
+            Self::Mul(left, right)
+            | Self::Add(left, right)
+            | Self::Sub(left, right)
+            | Self::Div(left, right)
+            | Self::Min(left, right)
+            | Self::Max(left, right) => left.is_auto() || right.is_auto(),
+            Self::Clamp(min, value, max) => min.is_auto() || value.is_auto() || max.is_auto(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Length {
+    Auto,
+    Px(f32),
+    Percent(f32),
+    /// Percent of the viewport width.
+    Vw(f32),
+    /// Percent of the viewport height.
+    Vh(f32),
+    /// Percent of the smaller viewport dimension.
+    Vmin(f32),
+    /// Percent of the larger viewport dimension.
+    Vmax(f32),
+    /// Multiple of the font size of the node.
+    Em(f32),
+    /// Multiple of the font size of the root node.
+    Rem(f32),
+    /// Width of the node itself.
+    Width,
+    /// Height of the node itself.
+    Height,
+    ParentWidth,
+    ParentHeight,
+    ViewportWidth,
+    ViewportHeight,
+    /// Font size of the node, i.e. `1em`.
+    FontSize,
+    Dynamic(Box<Operation>),
+}
+
+impl Length {
+    #[must_use]
+    pub fn is_auto(&self) -> bool {
+        match self {
+            Self::Auto => true,
+            Self::Dynamic(operation) => operation.is_auto(),
+            _ => false,
+        }
+    }
+}
+
+impl From<Operation> for Length {
+    fn from(value: Operation) -> Self {
+        Self::Dynamic(Box::new(value))
+    }
+}
+
+macro_rules! impl_operation {
+    ($trait:ident, $func:ident, $operation:ident) => {
+        impl $trait for Length {
+            type Output = Self;
+
+            fn $func(self, rhs: Self) -> Self::Output {
+                Self::from(Operation::$operation(self, rhs))
+            }
+        }
+
+        impl $trait<f32> for Length {
+            type Output = Self;
+
+            fn $func(self, rhs: f32) -> Self::Output {
+                Self::from(Operation::$operation(self, Self::Px(rhs)))
+            }
+        }
+    };
+}
+
+impl_operation!(Add, add, Add);
+impl_operation!(Sub, sub, Sub);
+impl_operation!(Mul, mul, Mul);
+impl_operation!(Div, div, Div);
+
+#[must_use]
+pub const fn px(value: f32) -> Length {
+    Length::Px(value)
+}
+
+#[must_use]
+pub const fn percent(value: f32) -> Length {
+    Length::Percent(value)
+}
+
+#[must_use]
+pub const fn vw(value: f32) -> Length {
+    Length::Vw(value)
+}
+
+#[must_use]
+pub const fn vh(value: f32) -> Length {
+    Length::Vh(value)
+}
+
+#[must_use]
+pub const fn vmin(value: f32) -> Length {
+    Length::Vmin(value)
+}
+
+#[must_use]
+pub const fn vmax(value: f32) -> Length {
+    Length::Vmax(value)
+}
+
+#[must_use]
+pub const fn em(value: f32) -> Length {
+    Length::Em(value)
+}
+
+#[must_use]
+pub const fn rem(value: f32) -> Length {
+    Length::Rem(value)
+}
+
+#[must_use]
+pub const fn width() -> Length {
+    Length::Width
+}
+
+#[must_use]
+pub const fn height() -> Length {
+    Length::Height
+}
+
+#[must_use]
+pub const fn parent_width() -> Length {
+    Length::ParentWidth
+}
+
+#[must_use]
+pub const fn parent_height() -> Length {
+    Length::ParentHeight
+}
+
+#[must_use]
+pub const fn viewport_width() -> Length {
+    Length::ViewportWidth
+}
+
+#[must_use]
+pub const fn viewport_height() -> Length {
+    Length::ViewportHeight
+}
+
+#[must_use]
+pub const fn font_size() -> Length {
+    Length::FontSize
+}
+
+#[must_use]
+pub const fn auto() -> Length {
+    Length::Auto
+}
+
+#[must_use]
+pub fn dynamic(operation: Operation) -> Length {
+    Length::from(operation)
+}
+
+#[must_use]
+pub fn min(a: Length, b: Length) -> Length {
+    Length::from(Operation::Min(a, b))
+}
+
+#[must_use]
+pub fn max(a: Length, b: Length) -> Length {
+    Length::from(Operation::Max(a, b))
+}
+
+#[must_use]
+pub fn clamp(min: Length, value: Length, max: Length) -> Length {
+    Length::from(Operation::Clamp(min, value, max))
+}
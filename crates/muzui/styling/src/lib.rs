@@ -1,122 +1,173 @@
 #![warn(clippy::all, clippy::pedantic, clippy::nursery)]
 #![allow(clippy::module_name_repetitions)]
 
-pub use self::{colors::Color, font::*, position::Position, thickness::Thickness};
-
+use serde::Serialize;
+
+pub use self::{
+    animation::{Easing, Interpolate, Keyframes},
+    colors::Color,
+    font::*,
+    grid::GridFlow,
+    length::*,
+    position::Position,
+    stylesheet::{Rule, Selector, Stylesheet},
+    text::*,
+    thickness::Thickness,
+    transform::{TransformOrigin, Vector},
+};
+
+mod animation;
 mod colors;
 mod font;
+mod grid;
+mod length;
 mod position;
+mod stylesheet;
+mod text;
 mod thickness;
+mod transform;
 
-#[derive(Debug, Clone)]
-pub enum Operation {
-    Add(Length, Length),
-    Sub(Length, Length),
-    Mul(Length, Length),
-    Div(Length, Length),
-}
-
-impl Operation {
-    fn is_auto(&self) -> bool {
-        match self {
-            Self::Mul(left, right)
-            | Self::Add(left, right)
-            | Self::Sub(left, right)
-            | Self::Div(left, right) => left.is_auto() || right.is_auto(),
-        }
-    }
-}
-
-#[derive(Debug, Clone)]
-pub enum Length {
-    Auto,
-    Px(f32),
-    Percent(f32),
-    ParentWidth,
-    ParentHeight,
-    Dynamic(Vec<Operation>),
-}
-
-impl Length {
-    #[must_use]
-    pub fn is_auto(&self) -> bool {
-        matches!(self, Self::Auto) || self.is_dynamic_and(|ops| ops.iter().any(Operation::is_auto))
-    }
-
-    fn is_dynamic_and<F: Fn(&[Operation]) -> bool>(&self, func: F) -> bool {
-        if let Self::Dynamic(ops) = self {
-            func(ops)
-        } else {
-            false
-        }
-    }
-}
-
-#[must_use]
-pub const fn px(value: f32) -> Length {
-    Length::Px(value)
-}
-
-#[must_use]
-pub const fn percent(value: f32) -> Length {
-    Length::Percent(value)
-}
-
-#[must_use]
-pub const fn parent_width() -> Length {
-    Length::ParentWidth
-}
-
-#[must_use]
-pub const fn parent_height() -> Length {
-    Length::ParentHeight
-}
-
-#[must_use]
-pub const fn auto() -> Length {
-    Length::Auto
-}
-
-pub fn dynamic<T: IntoIterator<Item = Operation>>(ops: T) -> Length {
-    Length::Dynamic(ops.into_iter().collect())
-}
-
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize)]
 pub struct Style {
-    pub margin: Thickness,
-    pub padding: Thickness,
-    pub row: usize,
-    pub row_span: usize,
-    pub column: usize,
-    pub column_span: usize,
+    pub margin: Option<Thickness>,
+    pub padding: Option<Thickness>,
+    /// Grid row, children without one are placed automatically.
+    pub row: Option<usize>,
+    /// Number of grid rows taken, one when not set.
+    pub row_span: Option<usize>,
+    /// Grid column, children without one are placed automatically.
+    pub column: Option<usize>,
+    /// Number of grid columns taken, one when not set.
+    pub column_span: Option<usize>,
+    /// Named grid area, takes precedence over `row` and `column`.
+    pub area: Option<String>,
     pub width: Option<Length>,
     pub height: Option<Length>,
     pub background: Option<Color>,
+    /// Inherited by children when not set.
     pub color: Option<Color>,
-    pub font_family: FontFamily,
-    pub font_weight: FontWeight,
-    pub font_slant: FontSlant,
-    pub font_size: FontSize,
-    pub corner_radius: Thickness,
-    pub position: Position,
+    /// Inherited by children when not set.
+    pub font_family: Option<FontFamily>,
+    /// Inherited by children when not set.
+    pub font_weight: Option<FontWeight>,
+    /// Inherited by children when not set.
+    pub font_slant: Option<FontSlant>,
+    /// Inherited by children when not set.
+    pub font_size: Option<FontSize>,
+    /// Inherited by children when not set.
+    pub font_width: Option<FontWidth>,
+    /// Inherited by children when not set.
+    pub text_align: Option<TextAlign>,
+    /// Height of a line as a multiple of the font size, inherited by children when not set.
+    pub line_height: Option<f32>,
+    /// Inherited by children when not set.
+    pub letter_spacing: Option<f32>,
+    /// Inherited by children when not set.
+    pub word_spacing: Option<f32>,
+    /// Inherited by children when not set.
+    pub text_transform: Option<TextTransform>,
+    pub text_decoration: Option<TextDecoration>,
+    /// Shadows drawn below the text, the first one on top, inherited by children when not set.
+    pub text_shadow: Option<Vec<TextShadow>>,
+    /// Inherited by children when not set.
+    pub text_stroke: Option<TextStroke>,
+    /// Lines after this one are cut off and the last line ends with an ellipsis.
+    pub max_lines: Option<usize>,
+    pub corner_radius: Option<Thickness>,
+    pub position: Option<Position>,
+    /// Offset of an absolutely positioned node from the border box of its parent.
     pub x: Option<f32>,
+    /// Offset of an absolutely positioned node from the border box of its parent.
     pub y: Option<f32>,
+    /// Transforms only change how the node is drawn, its layout and its siblings stay the same.
+    /// They're applied in the order translate, rotate, scale, skew.
+    pub translate: Option<Vector>,
+    /// Clockwise rotation in degrees.
+    pub rotate: Option<f32>,
+    pub scale: Option<Vector>,
+    /// Skew angles in degrees.
+    pub skew: Option<Vector>,
+    pub transform_origin: Option<TransformOrigin>,
+    /// Identifies the node within its tree, e.g. to render it on its own.
+    pub id: Option<String>,
+    /// Tells apart siblings, e.g. items built from a list, only needs to be unique among them.
+    pub key: Option<String>,
+    /// Classes matched by [`Stylesheet`] rules.
+    pub classes: Vec<String>,
+    /// States matched by [`Stylesheet`] rules, e.g. `selected` or `disabled`.
+    pub states: Vec<String>,
 }
 
 impl Style {
     #[must_use]
     pub fn new() -> Self {
-        Self {
-            row_span: 1,
-            column_span: 1,
-            ..Default::default()
-        }
+        Self::default()
     }
 
     #[must_use]
     pub fn builder() -> StyleBuilder {
         StyleBuilder { style: Self::new() }
     }
+
+    /// Takes every inherited property that isn't set on this style from `parent`.
+    pub fn inherit(&mut self, parent: &Self) {
+        self.color = self.color.or(parent.color);
+        self.font_family = self.font_family.take().or_else(|| parent.font_family.clone());
+        self.font_weight = self.font_weight.or(parent.font_weight);
+        self.font_slant = self.font_slant.or(parent.font_slant);
+        self.font_size = self.font_size.or(parent.font_size);
+        self.font_width = self.font_width.or(parent.font_width);
+        self.text_align = self.text_align.or(parent.text_align);
+        self.line_height = self.line_height.or(parent.line_height);
+        self.letter_spacing = self.letter_spacing.or(parent.letter_spacing);
+        self.word_spacing = self.word_spacing.or(parent.word_spacing);
+        self.text_transform = self.text_transform.or(parent.text_transform);
+        self.text_shadow = self.text_shadow.take().or_else(|| parent.text_shadow.clone());
+        self.text_stroke = self.text_stroke.or(parent.text_stroke);
+    }
+
+    /// Overrides every property that is set on `other`.
+    pub fn merge(&mut self, other: &Self) {
+        self.margin = other.margin.or(self.margin);
+        self.padding = other.padding.or(self.padding);
+        self.corner_radius = other.corner_radius.or(self.corner_radius);
+        self.row_span = other.row_span.or(self.row_span);
+        self.column_span = other.column_span.or(self.column_span);
+        self.position = other.position.or(self.position);
+        self.row = other.row.or(self.row);
+        self.column = other.column.or(self.column);
+        self.area = other.area.clone().or_else(|| self.area.take());
+        self.width = other.width.clone().or_else(|| self.width.take());
+        self.height = other.height.clone().or_else(|| self.height.take());
+        self.background = other.background.or(self.background);
+        self.color = other.color.or(self.color);
+        self.font_family = other.font_family.clone().or_else(|| self.font_family.take());
+        self.font_weight = other.font_weight.or(self.font_weight);
+        self.font_slant = other.font_slant.or(self.font_slant);
+        self.font_size = other.font_size.or(self.font_size);
+        self.font_width = other.font_width.or(self.font_width);
+        self.text_align = other.text_align.or(self.text_align);
+        self.line_height = other.line_height.or(self.line_height);
+        self.letter_spacing = other.letter_spacing.or(self.letter_spacing);
+        self.word_spacing = other.word_spacing.or(self.word_spacing);
+        self.text_transform = other.text_transform.or(self.text_transform);
+        self.text_decoration = other.text_decoration.or(self.text_decoration);
+        self.text_shadow = other.text_shadow.clone().or_else(|| self.text_shadow.take());
+        self.text_stroke = other.text_stroke.or(self.text_stroke);
+        self.max_lines = other.max_lines.or(self.max_lines);
+        self.x = other.x.or(self.x);
+        self.y = other.y.or(self.y);
+        self.translate = other.translate.or(self.translate);
+        self.rotate = other.rotate.or(self.rotate);
+        self.scale = other.scale.or(self.scale);
+        self.skew = other.skew.or(self.skew);
+        self.transform_origin = other
+            .transform_origin
+            .clone()
+            .or_else(|| self.transform_origin.take());
+        self.id = other.id.clone().or_else(|| self.id.take());
+        self.key = other.key.clone().or_else(|| self.key.take());
+    }
 }
 
 #[derive(Default)]
@@ -132,49 +183,56 @@ impl StyleBuilder {
 
     #[must_use]
     pub fn margin<T: Into<Thickness>>(mut self, value: T) -> Self {
-        self.style.margin = value.into();
+        self.style.margin = Some(value.into());
 
         self
     }
 
     #[must_use]
     pub fn padding<T: Into<Thickness>>(mut self, value: T) -> Self {
-        self.style.padding = value.into();
+        self.style.padding = Some(value.into());
 
         self
     }
 
     #[must_use]
     pub fn corner_radius<T: Into<Thickness>>(mut self, value: T) -> Self {
-        self.style.corner_radius = value.into();
+        self.style.corner_radius = Some(value.into());
 
         self
     }
 
     #[must_use]
-    pub const fn row(mut self, value: usize) -> Self {
-        self.style.row = value;
+    pub fn row<T: Into<Option<usize>>>(mut self, value: T) -> Self {
+        self.style.row = value.into();
 
         self
     }
 
     #[must_use]
     pub const fn row_span(mut self, value: usize) -> Self {
-        self.style.row_span = value;
+        self.style.row_span = Some(value);
 
         self
     }
 
     #[must_use]
-    pub const fn column(mut self, value: usize) -> Self {
-        self.style.column = value;
+    pub fn column<T: Into<Option<usize>>>(mut self, value: T) -> Self {
+        self.style.column = value.into();
 
         self
     }
 
     #[must_use]
     pub const fn column_span(mut self, value: usize) -> Self {
-        self.style.column_span = value;
+        self.style.column_span = Some(value);
+
+        self
+    }
+
+    #[must_use]
+    pub fn area<T: Into<String>>(mut self, value: T) -> Self {
+        self.style.area = Some(value.into());
 
         self
     }
@@ -193,30 +251,106 @@ impl StyleBuilder {
         self
     }
 
+    /// Takes a single family or a comma separated fallback list, e.g. `"Inter, Noto Color Emoji"`.
     #[must_use]
     pub fn font_family<T: Into<String>>(mut self, family: T) -> Self {
-        self.style.font_family = FontFamily::new(family);
+        self.style.font_family = Some(FontFamily::new(family));
 
         self
     }
 
     #[must_use]
-    pub fn font_size(mut self, size: f32) -> Self {
-        self.style.font_size = FontSize::new(size);
+    pub const fn font_size(mut self, size: f32) -> Self {
+        self.style.font_size = Some(FontSize::new(size));
 
         self
     }
 
+    /// Takes a [`FontWeight`] or a numeric weight, e.g. `600`.
     #[must_use]
-    pub const fn font_weight(mut self, value: FontWeight) -> Self {
-        self.style.font_weight = value;
+    pub fn font_weight<T: Into<FontWeight>>(mut self, value: T) -> Self {
+        self.style.font_weight = Some(value.into());
 
         self
     }
 
     #[must_use]
     pub const fn font_slant(mut self, value: FontSlant) -> Self {
-        self.style.font_slant = value;
+        self.style.font_slant = Some(value);
+
+        self
+    }
+
+    #[must_use]
+    pub const fn font_width(mut self, value: FontWidth) -> Self {
+        self.style.font_width = Some(value);
+
+        self
+    }
+
+    #[must_use]
+    pub const fn text_align(mut self, value: TextAlign) -> Self {
+        self.style.text_align = Some(value);
+
+        self
+    }
+
+    #[must_use]
+    pub const fn line_height(mut self, value: f32) -> Self {
+        self.style.line_height = Some(value);
+
+        self
+    }
+
+    #[must_use]
+    pub const fn letter_spacing(mut self, value: f32) -> Self {
+        self.style.letter_spacing = Some(value);
+
+        self
+    }
+
+    #[must_use]
+    pub const fn word_spacing(mut self, value: f32) -> Self {
+        self.style.word_spacing = Some(value);
+
+        self
+    }
+
+    #[must_use]
+    pub const fn text_decoration(mut self, value: TextDecoration) -> Self {
+        self.style.text_decoration = Some(value);
+
+        self
+    }
+
+    /// Adds a shadow, shadows added earlier are drawn on top.
+    #[must_use]
+    pub fn text_shadow(mut self, value: TextShadow) -> Self {
+        self.style
+            .text_shadow
+            .get_or_insert_with(Vec::new)
+            .push(value);
+
+        self
+    }
+
+    #[must_use]
+    pub fn text_stroke<T: Into<Color>>(mut self, width: f32, color: T) -> Self {
+        self.style.text_stroke = Some(TextStroke::new(width, color));
+
+        self
+    }
+
+    #[must_use]
+    pub const fn text_transform(mut self, value: TextTransform) -> Self {
+        self.style.text_transform = Some(value);
+
+        self
+    }
+
+    #[must_use]
+    pub const fn max_lines(mut self, value: usize) -> Self {
+        self.style.max_lines = Some(value);
 
         self
     }
@@ -242,7 +376,7 @@ impl StyleBuilder {
 
     #[must_use]
     pub const fn position(mut self, value: Position) -> Self {
-        self.style.position = value;
+        self.style.position = Some(value);
 
         self
     }
@@ -261,8 +395,112 @@ impl StyleBuilder {
         self
     }
 
+    #[must_use]
+    pub fn translate<T: Into<Vector>>(mut self, value: T) -> Self {
+        self.style.translate = Some(value.into());
+
+        self
+    }
+
+    /// Rotates clockwise by `value` degrees.
+    #[must_use]
+    pub const fn rotate(mut self, value: f32) -> Self {
+        self.style.rotate = Some(value);
+
+        self
+    }
+
+    #[must_use]
+    pub fn scale<T: Into<Vector>>(mut self, value: T) -> Self {
+        self.style.scale = Some(value.into());
+
+        self
+    }
+
+    /// Skews by angles in degrees.
+    #[must_use]
+    pub fn skew<T: Into<Vector>>(mut self, value: T) -> Self {
+        self.style.skew = Some(value.into());
+
+        self
+    }
+
+    #[must_use]
+    pub fn transform_origin<T: Into<TransformOrigin>>(mut self, value: T) -> Self {
+        self.style.transform_origin = Some(value.into());
+
+        self
+    }
+
+    #[must_use]
+    pub fn id<T: Into<String>>(mut self, value: T) -> Self {
+        self.style.id = Some(value.into());
+
+        self
+    }
+
+    #[must_use]
+    pub fn key<T: Into<String>>(mut self, value: T) -> Self {
+        self.style.key = Some(value.into());
+
+        self
+    }
+
+    /// Adds whitespace separated classes, e.g. `"card primary"`.
+    #[must_use]
+    pub fn class<T: AsRef<str>>(mut self, value: T) -> Self {
+        self.style
+            .classes
+            .extend(value.as_ref().split_whitespace().map(ToString::to_string));
+
+        self
+    }
+
+    #[must_use]
+    pub fn state<T: Into<String>>(mut self, value: T) -> Self {
+        self.style.states.push(value.into());
+
+        self
+    }
+
     #[must_use]
     pub fn build(self) -> Style {
         self.style
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{FontWeight, Style, TextAlign, TextDecoration};
+
+    #[test]
+    fn test_inherit() {
+        let parent = Style::builder()
+            .font_family("Source Code Pro")
+            .font_size(32.0)
+            .color(0x00FF_0000)
+            .background(0x0000_FF00)
+            .text_align(TextAlign::Center)
+            .text_decoration(TextDecoration::UNDERLINE)
+            .build();
+
+        let mut style = Style::builder()
+            .font_size(16.0)
+            .font_weight(FontWeight::Bold)
+            .build();
+
+        style.inherit(&parent);
+
+        assert_eq!(
+            style.font_family.map(|family| family.family).as_deref(),
+            Some("Source Code Pro")
+        );
+        assert_eq!(style.font_size.map(|size| size.size), Some(16.0));
+        assert!(matches!(style.font_weight, Some(FontWeight::Bold)));
+        assert_eq!(style.color.map(|color| color.as_u32()), Some(0xFFFF_0000));
+        assert!(style.background.is_none());
+        assert_eq!(style.text_align, Some(TextAlign::Center));
+        assert!(style.text_decoration.is_none());
+        assert_eq!(FontWeight::from(700).value(), FontWeight::Bold.value());
+    }
+}
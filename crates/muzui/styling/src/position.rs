@@ -1,6 +1,12 @@
-#[derive(Debug, Default, Clone, Copy)]
+use serde::Serialize;
+
+/// How a container places a node.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum Position {
+    /// Placed at `x` and `y` from the top left corner of the parent's border box, siblings
+    /// ignore it. These used to be canvas coordinates, which only match for children of the root.
     Absolute,
+    /// Placed by the container after its previous relative siblings.
     #[default]
     Relative,
 }
@@ -0,0 +1,249 @@
+use crate::Style;
+
+/// Matches nodes by element type, classes and states, e.g. `Text.title:first-child`.
+///
+/// Every part is optional, an empty selector (or `*`) matches everything.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Selector {
+    pub element: Option<String>,
+    pub classes: Vec<String>,
+    pub states: Vec<String>,
+}
+
+impl Selector {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn element<T: Into<String>>(mut self, value: T) -> Self {
+        self.element = Some(value.into());
+
+        self
+    }
+
+    #[must_use]
+    pub fn class<T: Into<String>>(mut self, value: T) -> Self {
+        self.classes.push(value.into());
+
+        self
+    }
+
+    #[must_use]
+    pub fn state<T: Into<String>>(mut self, value: T) -> Self {
+        self.states.push(value.into());
+
+        self
+    }
+
+    /// Parses selectors written as `Element.class:state`.
+    #[must_use]
+    pub fn parse(value: &str) -> Self {
+        let mut selector = Self::new();
+        let mut rest = value.trim();
+
+        let end = rest.find(['.', ':']).unwrap_or(rest.len());
+
+        if !matches!(&rest[..end], "" | "*") {
+            selector.element = Some(rest[..end].to_string());
+        }
+
+        rest = &rest[end..];
+
+        while let Some(kind) = rest.chars().next() {
+            let end = rest[1..].find(['.', ':']).map_or(rest.len(), |end| end + 1);
+            let name = rest[1..end].to_string();
+
+            if kind == '.' {
+                selector.classes.push(name);
+            } else {
+                selector.states.push(name);
+            }
+
+            rest = &rest[end..];
+        }
+
+        selector
+    }
+
+    /// Classes and states weigh more than the element type.
+    #[must_use]
+    pub fn specificity(&self) -> (usize, usize) {
+        (
+            self.classes.len() + self.states.len(),
+            usize::from(self.element.is_some()),
+        )
+    }
+
+    /// Checks the selector against a node of type `element` with `style`.
+    ///
+    /// `states` are the states derived from the node's place in the tree, such as `first-child`,
+    /// they're checked along with [`Style::states`].
+    #[must_use]
+    pub fn matches(&self, element: &str, style: &Style, states: &[&str]) -> bool {
+        self.element.as_deref().is_none_or(|value| value == element)
+            && self
+                .classes
+                .iter()
+                .all(|class| style.classes.contains(class))
+            && self
+                .states
+                .iter()
+                .all(|state| style.states.contains(state) || states.contains(&state.as_str()))
+    }
+}
+
+impl From<&str> for Selector {
+    fn from(value: &str) -> Self {
+        Self::parse(value)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub selector: Selector,
+    pub style: Style,
+}
+
+/// An ordered list of rules applied to nodes before layout.
+///
+/// Matching rules are applied from the least to the most specific one, rules of equal
+/// specificity in the order they were added. The node's own style always wins.
+#[derive(Debug, Default, Clone)]
+pub struct Stylesheet {
+    pub rules: Vec<Rule>,
+}
+
+impl Stylesheet {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn rule<T: Into<Selector>>(mut self, selector: T, style: Style) -> Self {
+        self.add(selector, style);
+
+        self
+    }
+
+    pub fn add<T: Into<Selector>>(&mut self, selector: T, style: Style) {
+        self.rules.push(Rule {
+            selector: selector.into(),
+            style,
+        });
+    }
+
+    /// Appends the rules of `other`, so they take precedence over the existing ones of equal specificity.
+    pub fn extend(&mut self, other: Self) {
+        self.rules.extend(other.rules);
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Resolves the final style of a node of type `element`.
+    #[must_use]
+    pub fn compute(&self, element: &str, style: &Style, states: &[&str]) -> Style {
+        let mut rules = self
+            .rules
+            .iter()
+            .filter(|rule| rule.selector.matches(element, style, states))
+            .collect::<Vec<_>>();
+
+        rules.sort_by_key(|rule| rule.selector.specificity());
+
+        let mut computed = Style::new();
+
+        for rule in rules {
+            computed.merge(&rule.style);
+        }
+
+        computed.merge(style);
+        computed.classes.clone_from(&style.classes);
+        computed.states.clone_from(&style.states);
+
+        computed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Length, Position, Style};
+
+    use super::{Selector, Stylesheet};
+
+    #[test]
+    fn test_selector() {
+        let selector = Selector::parse("Text.title.primary:first-child");
+
+        assert_eq!(selector.element.as_deref(), Some("Text"));
+        assert_eq!(selector.classes, ["title", "primary"]);
+        assert_eq!(selector.states, ["first-child"]);
+        assert_eq!(selector.specificity(), (3, 1));
+
+        assert_eq!(Selector::parse("*"), Selector::new());
+        assert_eq!(Selector::parse(".card"), Selector::new().class("card"));
+    }
+
+    #[test]
+    fn test_precedence() {
+        let stylesheet = Stylesheet::new()
+            .rule(
+                ".card",
+                Style::builder().padding(16.0).font_size(24.0).build(),
+            )
+            .rule("Column", Style::builder().padding(8.0).margin(4.0).build())
+            .rule(":selected", Style::builder().font_size(32.0).build())
+            .rule(".card", Style::builder().background(0x00FF_0000).build());
+
+        let style = Style::builder()
+            .class("card")
+            .width(Length::Px(100.0))
+            .font_size(12.0)
+            .build();
+
+        let computed = stylesheet.compute("Column", &style, &["selected"]);
+
+        assert_eq!(computed.padding, Some(16.0.into()));
+        assert_eq!(computed.margin, Some(4.0.into()));
+        assert_eq!(
+            computed.background.map(|color| color.as_u32()),
+            Some(0xFFFF_0000)
+        );
+        assert_eq!(computed.font_size.map(|size| size.size), Some(12.0));
+        assert_eq!(computed.width, Some(Length::Px(100.0)));
+        assert_eq!(computed.classes, ["card"]);
+
+        let computed = stylesheet.compute("Row", &Style::new(), &[]);
+
+        assert_eq!(computed.padding, None);
+        assert!(computed.background.is_none());
+    }
+
+    #[test]
+    fn test_inline_defaults() {
+        let stylesheet = Stylesheet::new().rule(
+            ".card",
+            Style::builder()
+                .margin(8.0)
+                .position(Position::Absolute)
+                .build(),
+        );
+
+        let style = Style::builder()
+            .class("card")
+            .margin(0.0)
+            .position(Position::Relative)
+            .build();
+
+        let computed = stylesheet.compute("Column", &style, &[]);
+
+        // Values equal to the defaults are still set and win over the stylesheet.
+        assert_eq!(computed.margin, Some(0.0.into()));
+        assert_eq!(computed.position, Some(Position::Relative));
+    }
+}
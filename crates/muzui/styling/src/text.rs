@@ -0,0 +1,146 @@
+use serde::Serialize;
+
+use crate::Color;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum TextAlign {
+    Left,
+    Right,
+    Center,
+    Justify,
+    #[default]
+    Start,
+    End,
+}
+
+/// Lines drawn along the text, any of them can be combined.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct TextDecoration {
+    pub underline: bool,
+    pub overline: bool,
+    pub line_through: bool,
+}
+
+impl TextDecoration {
+    pub const NONE: Self = Self {
+        underline: false,
+        overline: false,
+        line_through: false,
+    };
+
+    pub const UNDERLINE: Self = Self {
+        underline: true,
+        ..Self::NONE
+    };
+
+    pub const OVERLINE: Self = Self {
+        overline: true,
+        ..Self::NONE
+    };
+
+    pub const LINE_THROUGH: Self = Self {
+        line_through: true,
+        ..Self::NONE
+    };
+
+    /// Combines the lines of both decorations.
+    #[must_use]
+    pub const fn with(self, other: Self) -> Self {
+        Self {
+            underline: self.underline || other.underline,
+            overline: self.overline || other.overline,
+            line_through: self.line_through || other.line_through,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct TextShadow {
+    pub color: Color,
+    pub x: f32,
+    pub y: f32,
+    /// Blur radius, `0` for a sharp shadow.
+    pub blur: f32,
+}
+
+impl TextShadow {
+    #[must_use]
+    pub fn new<T: Into<Color>>(color: T, x: f32, y: f32, blur: f32) -> Self {
+        Self {
+            color: color.into(),
+            x,
+            y,
+            blur,
+        }
+    }
+}
+
+/// An outline drawn around the glyphs, centered on their edges.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct TextStroke {
+    pub width: f32,
+    pub color: Color,
+}
+
+impl TextStroke {
+    #[must_use]
+    pub fn new<T: Into<Color>>(width: f32, color: T) -> Self {
+        Self {
+            width,
+            color: color.into(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum TextTransform {
+    #[default]
+    None,
+    Uppercase,
+    Lowercase,
+    /// Uppercases the first letter of every word.
+    Capitalize,
+}
+
+impl TextTransform {
+    #[must_use]
+    pub fn apply(self, text: &str) -> String {
+        match self {
+            Self::None => text.to_string(),
+            Self::Uppercase => text.to_uppercase(),
+            Self::Lowercase => text.to_lowercase(),
+            Self::Capitalize => {
+                let mut word_start = true;
+
+                text.chars()
+                    .flat_map(|char| {
+                        let capitalize = word_start;
+
+                        word_start = char.is_whitespace();
+
+                        if capitalize {
+                            char.to_uppercase().collect::<Vec<_>>()
+                        } else {
+                            vec![char]
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextTransform;
+
+    #[test]
+    fn test_transform() {
+        assert_eq!(TextTransform::Uppercase.apply("Lv. 90"), "LV. 90");
+        assert_eq!(TextTransform::Lowercase.apply("Lv. 90"), "lv. 90");
+        assert_eq!(
+            TextTransform::Capitalize.apply("raiden  shogun ßig"),
+            "Raiden  Shogun SSig"
+        );
+    }
+}
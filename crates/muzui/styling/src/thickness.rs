@@ -1,6 +1,7 @@
 use muzui_geometry::Rect;
+use serde::Serialize;
 
-#[derive(Default, Debug, Clone, Copy)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct Thickness {
     pub left: f32,
     pub top: f32,
@@ -0,0 +1,68 @@
+use serde::Serialize;
+
+use crate::Length;
+
+/// A pair of values along the x and y axes, a single value is used for both.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct Vector {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vector {
+    #[must_use]
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<f32> for Vector {
+    fn from(value: f32) -> Self {
+        Self::new(value, value)
+    }
+}
+
+impl From<[f32; 2]> for Vector {
+    fn from([x, y]: [f32; 2]) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<(f32, f32)> for Vector {
+    fn from((x, y): (f32, f32)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+/// The point transforms are applied around, percentages refer to the border box of the node.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TransformOrigin {
+    pub x: Length,
+    pub y: Length,
+}
+
+impl TransformOrigin {
+    #[must_use]
+    pub const fn new(x: Length, y: Length) -> Self {
+        Self { x, y }
+    }
+}
+
+/// The center of the node.
+impl Default for TransformOrigin {
+    fn default() -> Self {
+        Self::new(Length::Percent(50.0), Length::Percent(50.0))
+    }
+}
+
+impl From<[Length; 2]> for TransformOrigin {
+    fn from([x, y]: [Length; 2]) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<(Length, Length)> for TransformOrigin {
+    fn from((x, y): (Length, Length)) -> Self {
+        Self::new(x, y)
+    }
+}
@@ -40,6 +40,8 @@ type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
 type State = Arc<StateRef>;
 
 const MUSIC_NOTE: &[u8] = include_bytes!("/home/aiving/music_note.svg");
+const TITLE_FONT: &str = "/usr/share/fonts/adobe-source-code-pro/SourceCodePro-Bold.otf";
+const ARTIST_FONT: &str = "/usr/share/fonts/adobe-source-code-pro/SourceCodePro-Regular.otf";
 
 struct StateRef {
     http: Arc<HttpClient>,
@@ -568,6 +570,16 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Loads the font file named by the `key` environment variable, or `default` if it isn't set.
+fn load_font(fonts: &FontMgr, key: &str, default: &str, size: f32) -> Result<Font> {
+    let path = var(key).unwrap_or_else(|_| default.to_string());
+    let typeface = fonts
+        .new_from_data(&fs::read(&path)?, None)
+        .ok_or_else(|| format!("{path} isn't a font"))?;
+
+    Ok(Font::from_typeface(typeface, size))
+}
+
 fn get_image(
     bg: Argb,
     image: Argb,
@@ -575,7 +587,7 @@ fn get_image(
     artist_color: Argb,
     metadata: Metadata,
     position: i64,
-) -> Option<Vec<u8>> {
+) -> Result<Option<Vec<u8>>> {
     let (width, height) = (480.0, 226.0);
     let mut surface = surfaces::raster_n32_premul((width as i32, height as i32))
         .expect("can't create skia surface");
@@ -626,14 +638,8 @@ fn get_image(
     }
 
     let fonts_manager = FontMgr::new();
-    let title_font =
-        fs::read("/usr/share/fonts/adobe-source-code-pro/SourceCodePro-Bold.otf").unwrap();
-    let artist_font =
-        fs::read("/usr/share/fonts/adobe-source-code-pro/SourceCodePro-Regular.otf").unwrap();
-    let title_typeface = fonts_manager.new_from_data(&title_font, None).expect("AA");
-    let artist_typeface = fonts_manager.new_from_data(&artist_font, None).expect("AA");
-    let title_font = Font::from_typeface(title_typeface, 24.0);
-    let artist_font = Font::from_typeface(artist_typeface, 18.0);
+    let title_font = load_font(&fonts_manager, "MUZIK_TITLE_FONT", TITLE_FONT, 24.0)?;
+    let artist_font = load_font(&fonts_manager, "MUZIK_ARTIST_FONT", ARTIST_FONT, 18.0)?;
 
     let title = metadata.title.expect("there is no title in metadata");
     let artists = metadata.artists.expect("there is no artists in metadata");
@@ -716,9 +722,9 @@ fn get_image(
 
     let image = surface.image_snapshot();
 
-    image
+    Ok(image
         .encode(None, EncodedImageFormat::PNG, 100)
-        .map(|data| data.to_vec())
+        .map(|data| data.to_vec()))
 }
 
 impl HoYoLab {
@@ -846,7 +852,7 @@ async fn handle_message(message: Box<MessageCreate>, state: State) -> Result<()>
                 theme.secondary,
                 metadata,
                 position,
-            );
+            )?;
 
             match image {
                 Some(data) => {